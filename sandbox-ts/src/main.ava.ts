@@ -1,6 +1,9 @@
 import { Worker, NearAccount } from "near-workspaces"
 import anyTest, { TestFn } from "ava"
 
+// 0.1 NEAR, any unused deposit is refunded by the contract
+const STORAGE_DEPOSIT = "100000000000000000000000"
+
 const test = anyTest as TestFn<{
   worker: Worker
   accounts: Record<string, NearAccount>
@@ -15,6 +18,7 @@ test.beforeEach(async (t) => {
   const contract = await root.createSubAccount("test-account")
   // Get wasm file path from package.json test script in folder above
   await contract.deploy(process.argv[2])
+  await contract.call(contract, "new", { owner_id: root.accountId })
 
   // Save state for test runs, it is unique for each test
   t.context.worker = worker
@@ -39,7 +43,8 @@ test("creates a new post", async (t) => {
     description: "Test Description",
     tags: "tag1,tag2,tag3",
     media: "post.png",
-  })
+    media_type: "image",
+  }, { attachedDeposit: STORAGE_DEPOSIT })
 
   t.is(post.title, "Test")
   t.is(post.tags[2], "tag3")
@@ -48,26 +53,36 @@ test("creates a new post", async (t) => {
 test("gets all posts", async (t) => {
   const { root, contract } = t.context.accounts
 
+  // Post from separate accounts to stay clear of the post cooldown
+  const alice = await root.createSubAccount("alice")
+  const bob = await root.createSubAccount("bob")
+
   await root.call(contract, "add_post", {
     title: "Test0",
     description: "Test Description0",
     tags: "tag1,tag2,tag3",
     media: "post.png",
-  })
-  await root.call(contract, "add_post", {
+    media_type: "image",
+  }, { attachedDeposit: STORAGE_DEPOSIT })
+  await alice.call(contract, "add_post", {
     title: "Test1",
     description: "Test Description1",
     tags: "tag4,tag5,tag6",
     media: "post.png",
-  })
-  await root.call(contract, "add_post", {
+    media_type: "image",
+  }, { attachedDeposit: STORAGE_DEPOSIT })
+  await bob.call(contract, "add_post", {
     title: "Test2",
     description: "Test Description2",
     tags: "tag1,tag5,tag7",
     media: "post.png",
-  })
+    media_type: "image",
+  }, { attachedDeposit: STORAGE_DEPOSIT })
 
-  const allPosts: any = await contract.view("get_all_posts")
+  const allPosts: any = await contract.view("get_all_posts", {
+    from_index: 0,
+    limit: 10,
+  })
 
   t.is(allPosts[1][1].title, "Test1")
   t.is(allPosts[2][1].description, "Test Description2")
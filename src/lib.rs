@@ -2,8 +2,70 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::UnorderedMap;
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::{env, near_bindgen, AccountId};
+use near_sdk::serde_json::{self, json};
+use near_sdk::{env, near_bindgen, AccountId, BorshStorageKey, PanicOnDefault, Promise};
 
+const MAX_TITLE_LENGTH: usize = 200;
+const MAX_DESCRIPTION_LENGTH: usize = 2000;
+const MAX_MEDIA_PER_POST: usize = 10;
+const MAX_TAGS_PER_POST: usize = 10;
+const MAX_POSTS_PER_BATCH: usize = 10;
+const MAX_TIMELINE_BACKFILL: usize = 20;
+const MAX_EDIT_HISTORY: usize = 10;
+const MAX_COMMENT_LENGTH: usize = 1000;
+const MAX_DISPLAY_NAME_LENGTH: usize = 64;
+const MAX_BIO_LENGTH: usize = 500;
+const MAX_AVATAR_URL_LENGTH: usize = 500;
+const MAX_REPORT_REASON_LENGTH: usize = 500;
+const DEFAULT_REPORT_THRESHOLD: u64 = 3;
+const MIN_KEYWORD_LENGTH: usize = 3;
+const STOPWORDS: [&str; 12] = [
+    "the", "and", "for", "are", "but", "not", "you", "with", "this", "that", "from", "was",
+];
+// One hour in nanoseconds
+const DEFAULT_LIKE_NOTIFICATION_WINDOW: u64 = 60 * 60 * 1_000_000_000;
+// Ten seconds in nanoseconds
+const DEFAULT_POST_COOLDOWN: u64 = 10 * 1_000_000_000;
+
+const LIKE_WEIGHT: u64 = 3;
+const COMMENT_WEIGHT: u64 = 5;
+const VIEW_WEIGHT: u64 = 1;
+// Tips are weighted per whole NEAR
+const TIP_WEIGHT: u64 = 10;
+const YOCTO_PER_NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+
+const EVENT_STANDARD: &str = "social_network";
+const EVENT_VERSION: &str = "1.0.0";
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MediaType {
+    Image,
+    Video,
+    Audio,
+    Text,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Visibility {
+    Public,
+    FollowersOnly,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommentPolicy {
+    Anyone,
+    FollowersOnly,
+    Nobody,
+}
+
+// The state layout isn't compatible with earlier versions of the contract, upgrading requires a
+// fresh deploy
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 #[derive(Clone)]
@@ -12,309 +74,6434 @@ pub struct Post {
     title: String,
     description: String,
     tags: Vec<String>,
-    media: String,
+    media: Vec<String>,
+    media_type: MediaType,
     users_who_liked: Vec<AccountId>,
+    like_count: u64,
     owner_id: AccountId,
+    created_at: u64,
+    reposted_from: Option<u128>,
+    view_count: u64,
+    total_tips: u128,
+    // Timestamp and previous title of each edit, oldest first
+    edit_history: Vec<(u64, String)>,
+    deleted: bool,
+    visibility: Visibility,
+    // Drafts are only visible to their owner until published
+    published: bool,
+    // Scheduled posts stay hidden until this timestamp
+    publish_at: Option<u64>,
+    // Set once the post reaches the report threshold, pending review
+    auto_hidden: bool,
+    comment_policy: CommentPolicy,
+    // Number of times this post has been reposted
+    repost_count: u64,
+    // Quotes link to the post they quote through reposted_from
+    quote: bool,
+    // Deleting the post refunds the account that attached the storage deposit, up to what it paid
+    storage_payer: AccountId,
+    storage_paid: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone)]
+pub struct Comment {
+    id: u128,
+    post_id: u128,
+    author: AccountId,
+    text: String,
+    created_at: u64,
+    parent_comment_id: Option<u128>,
+    liked_by: Vec<AccountId>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reaction {
+    Like,
+    Love,
+    Laugh,
+    Angry,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotificationKind {
+    Like,
+    Follow,
+    Mention,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone)]
+pub struct Notification {
+    kind: NotificationKind,
+    actor: AccountId,
+    post_id: Option<u128>,
+    created_at: u64,
+    read: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Clone)]
+pub struct Profile {
+    display_name: String,
+    bio: String,
+    avatar_url: String,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+enum StorageKey {
+    Posts,
+    LikesByUserId,
+    PostsByTag,
+    PostsByOwner,
+    CommentsByPost,
+    Following,
+    Followers,
+    BookmarksByUser,
+    BlockedByUser,
+    Profiles,
+    PinnedPost,
+    Reports,
+    MentionsByUser,
+    Notifications,
+    Reactions,
+    PostsByKeyword,
+    TipsByPost,
+    Deactivated,
+    LastPostAt,
+    QuotesByPost,
+    PinnedComment,
+    Timeline,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AddPostArgs {
+    title: String,
+    description: String,
+    tags: String,
+    media: String,
+    media_type: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractStats {
+    total_posts: u64,
+    total_tags: u64,
+    total_likes: u64,
 }
 
 // Define the contract structure
 #[near_bindgen]
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct SocialNetworking {
     posts: UnorderedMap<u128, Post>,
     number_of_posts: u128,
-    likes_by_user_id: UnorderedMap<AccountId, Vec<Post>>,
-    posts_by_tag: UnorderedMap<String, Vec<Post>>,
+    likes_by_user_id: UnorderedMap<AccountId, Vec<u128>>,
+    posts_by_tag: UnorderedMap<String, Vec<u128>>,
+    posts_by_owner: UnorderedMap<AccountId, Vec<u128>>,
+    comments_by_post: UnorderedMap<u128, Vec<Comment>>,
+    number_of_comments: u128,
+    following: UnorderedMap<AccountId, Vec<AccountId>>,
+    followers: UnorderedMap<AccountId, Vec<AccountId>>,
+    bookmarks_by_user: UnorderedMap<AccountId, Vec<u128>>,
+    blocked_by_user: UnorderedMap<AccountId, Vec<AccountId>>,
+    profiles: UnorderedMap<AccountId, Profile>,
+    pinned_post: UnorderedMap<AccountId, u128>,
+    reports: UnorderedMap<u128, Vec<(AccountId, String)>>,
+    owner_id: AccountId,
+    mentions_by_user: UnorderedMap<AccountId, Vec<u128>>,
+    notifications: UnorderedMap<AccountId, Vec<Notification>>,
+    reactions: UnorderedMap<u128, Vec<(AccountId, Reaction)>>,
+    report_threshold: u64,
+    like_notification_window: u64,
+    posts_by_keyword: UnorderedMap<String, Vec<u128>>,
+    tips_by_post: UnorderedMap<u128, Vec<(AccountId, u128)>>,
+    deactivated: UnorderedMap<AccountId, bool>,
+    last_post_at: UnorderedMap<AccountId, u64>,
+    quotes_by_post: UnorderedMap<u128, Vec<u128>>,
+    pinned_comment: UnorderedMap<u128, u128>,
+    timeline: UnorderedMap<AccountId, Vec<u128>>,
 }
 
-impl Default for SocialNetworking {
-    fn default() -> Self {
-        Self {
-            posts: UnorderedMap::new(b'm'),
-            number_of_posts: 0,
-            likes_by_user_id: UnorderedMap::new(b'n'),
-            posts_by_tag: UnorderedMap::new(b'o'),
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+// Split a comma separated list into unique, normalized tags
+fn parse_tags(tags: &str) -> Vec<String> {
+    let mut parsed_tags = Vec::<String>::new();
+
+    for tag in tags.split(',') {
+        let tag = normalize_tag(tag);
+
+        if !tag.is_empty() && !parsed_tags.contains(&tag) {
+            parsed_tags.push(tag);
+        }
+    }
+
+    parsed_tags
+}
+
+fn validate_tags(tags: &[String]) {
+    if tags.len() > MAX_TAGS_PER_POST {
+        env::panic_str("TOO_MANY_TAGS");
+    }
+}
+
+// Find the #hashtags used in a block of text
+fn extract_hashtags(text: &str) -> Vec<String> {
+    let mut hashtags = Vec::<String>::new();
+
+    for word in text.split_whitespace() {
+        if let Some(hashtag) = word.strip_prefix('#') {
+            let hashtag: String = hashtag
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            let hashtag = normalize_tag(&hashtag);
+
+            if !hashtag.is_empty() && !hashtags.contains(&hashtag) {
+                hashtags.push(hashtag);
+            }
+        }
+    }
+
+    hashtags
+}
+
+// Split a block of text into the lowercased words used by the keyword index
+fn extract_keywords(text: &str) -> Vec<String> {
+    let mut keywords = Vec::<String>::new();
+
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let keyword = word.to_lowercase();
+
+        if keyword.chars().count() < MIN_KEYWORD_LENGTH || STOPWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+
+        if !keywords.contains(&keyword) {
+            keywords.push(keyword);
+        }
+    }
+
+    keywords
+}
+
+// Find the @mentions in a block of text that are valid account ids
+fn extract_mentions(text: &str) -> Vec<AccountId> {
+    let mut mentions = Vec::<AccountId>::new();
+
+    for word in text.split_whitespace() {
+        if let Some(mention) = word.strip_prefix('@') {
+            let mention: String = mention
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || "-_.".contains(*c))
+                .collect();
+
+            // Drop punctuation ending a sentence, e.g. "thanks @bob.near."
+            if let Ok(account_id) = mention.trim_end_matches('.').parse::<AccountId>() {
+                if !mentions.contains(&account_id) {
+                    mentions.push(account_id);
+                }
+            }
         }
     }
+
+    mentions
+}
+
+// Split a comma separated list of media URLs
+fn parse_media(media: &str) -> Vec<String> {
+    let parsed_media: Vec<String> = media
+        .split(',')
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .collect();
+
+    if parsed_media.len() > MAX_MEDIA_PER_POST {
+        env::panic_str("TOO_MANY_MEDIA");
+    }
+
+    parsed_media
+}
+
+fn parse_media_type(media_type: &str) -> MediaType {
+    match media_type.trim().to_lowercase().as_str() {
+        "image" => MediaType::Image,
+        "video" => MediaType::Video,
+        "audio" => MediaType::Audio,
+        "text" => MediaType::Text,
+        _ => env::panic_str("INVALID_MEDIA_TYPE"),
+    }
+}
+
+fn parse_visibility(visibility: &str) -> Visibility {
+    match visibility.trim().to_lowercase().as_str() {
+        "public" => Visibility::Public,
+        "followers_only" => Visibility::FollowersOnly,
+        _ => env::panic_str("INVALID_VISIBILITY"),
+    }
+}
+
+// Require the attached deposit to cover storage used since initial_storage_usage, refunding any excess
+fn pay_for_storage(initial_storage_usage: u64) -> u64 {
+    let storage_used = env::storage_usage().saturating_sub(initial_storage_usage);
+    let required_deposit = storage_used as u128 * env::storage_byte_cost();
+    let attached_deposit = env::attached_deposit();
+
+    if attached_deposit < required_deposit {
+        env::panic_str("INSUFFICIENT_STORAGE_DEPOSIT");
+    }
+
+    let refund = attached_deposit - required_deposit;
+    if refund > 0 {
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+    }
+
+    storage_used
+}
+
+fn validate_post(title: &str, description: &str) {
+    if title.trim().is_empty() {
+        env::panic_str("TITLE_REQUIRED");
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        env::panic_str("TITLE_TOO_LONG");
+    }
+    if description.trim().is_empty() {
+        env::panic_str("DESCRIPTION_REQUIRED");
+    }
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        env::panic_str("DESCRIPTION_TOO_LONG");
+    }
+}
+
+// Log an event following the NEP-297 standard
+fn log_event(event: &str, data: serde_json::Value) {
+    let event = json!({
+        "standard": EVENT_STANDARD,
+        "version": EVENT_VERSION,
+        "event": event,
+        "data": [data],
+    });
+
+    env::log_str(&format!("EVENT_JSON:{}", event));
 }
 
 #[near_bindgen]
 impl SocialNetworking {
+    #[init]
+    pub fn new(owner_id: AccountId) -> Self {
+        assert!(!env::state_exists(), "ALREADY_INITIALIZED");
+
+        Self {
+            posts: UnorderedMap::new(StorageKey::Posts),
+            number_of_posts: 0,
+            likes_by_user_id: UnorderedMap::new(StorageKey::LikesByUserId),
+            posts_by_tag: UnorderedMap::new(StorageKey::PostsByTag),
+            posts_by_owner: UnorderedMap::new(StorageKey::PostsByOwner),
+            comments_by_post: UnorderedMap::new(StorageKey::CommentsByPost),
+            number_of_comments: 0,
+            following: UnorderedMap::new(StorageKey::Following),
+            followers: UnorderedMap::new(StorageKey::Followers),
+            bookmarks_by_user: UnorderedMap::new(StorageKey::BookmarksByUser),
+            blocked_by_user: UnorderedMap::new(StorageKey::BlockedByUser),
+            profiles: UnorderedMap::new(StorageKey::Profiles),
+            pinned_post: UnorderedMap::new(StorageKey::PinnedPost),
+            reports: UnorderedMap::new(StorageKey::Reports),
+            owner_id,
+            mentions_by_user: UnorderedMap::new(StorageKey::MentionsByUser),
+            notifications: UnorderedMap::new(StorageKey::Notifications),
+            reactions: UnorderedMap::new(StorageKey::Reactions),
+            report_threshold: DEFAULT_REPORT_THRESHOLD,
+            like_notification_window: DEFAULT_LIKE_NOTIFICATION_WINDOW,
+            posts_by_keyword: UnorderedMap::new(StorageKey::PostsByKeyword),
+            tips_by_post: UnorderedMap::new(StorageKey::TipsByPost),
+            deactivated: UnorderedMap::new(StorageKey::Deactivated),
+            last_post_at: UnorderedMap::new(StorageKey::LastPostAt),
+            quotes_by_post: UnorderedMap::new(StorageKey::QuotesByPost),
+            pinned_comment: UnorderedMap::new(StorageKey::PinnedComment),
+            timeline: UnorderedMap::new(StorageKey::Timeline),
+        }
+    }
+
+    #[payable]
     pub fn add_post(
         &mut self,
         title: String,
         description: String,
         tags: String,
         media: String,
+        media_type: String,
+    ) -> Post {
+        let initial_storage_usage = env::storage_usage();
+
+        self.check_post_rate_limit();
+
+        let mut post = self.internal_add_post(
+            title,
+            description,
+            tags,
+            media,
+            media_type,
+            Visibility::Public,
+            true,
+        );
+
+        let storage_paid = pay_for_storage(initial_storage_usage);
+        self.record_storage_paid(&mut post, storage_paid);
+        post
+    }
+
+    #[payable]
+    pub fn add_post_with_visibility(
+        &mut self,
+        title: String,
+        description: String,
+        tags: String,
+        media: String,
+        media_type: String,
+        visibility: String,
+    ) -> Post {
+        let initial_storage_usage = env::storage_usage();
+
+        self.check_post_rate_limit();
+
+        let visibility = parse_visibility(&visibility);
+        let mut post = self.internal_add_post(
+            title,
+            description,
+            tags,
+            media,
+            media_type,
+            visibility,
+            true,
+        );
+
+        let storage_paid = pay_for_storage(initial_storage_usage);
+        self.record_storage_paid(&mut post, storage_paid);
+        post
+    }
+
+    #[payable]
+    pub fn add_draft(
+        &mut self,
+        title: String,
+        description: String,
+        tags: String,
+        media: String,
+        media_type: String,
     ) -> Post {
-        let tags_iterator = tags.split(",");
-        let mut tags = Vec::<String>::new();
-        for tag in tags_iterator {
-            tags.push(tag.to_string());
+        let initial_storage_usage = env::storage_usage();
+
+        let mut post = self.internal_add_post(
+            title,
+            description,
+            tags,
+            media,
+            media_type,
+            Visibility::Public,
+            false,
+        );
+
+        let storage_paid = pay_for_storage(initial_storage_usage);
+        self.record_storage_paid(&mut post, storage_paid);
+        post
+    }
+
+    pub fn publish_post(&mut self, post_id: u128) -> Post {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
         }
 
-        let post = Post {
-            id: self.number_of_posts,
+        if self.is_live(&post) {
+            env::panic_str("ALREADY_PUBLISHED");
+        }
+
+        self.check_post_rate_limit();
+
+        // Scheduled posts have already had their mentions indexed
+        let was_published = post.published;
+
+        // A draft counts as created when it's published
+        post.published = true;
+        post.publish_at = None;
+        post.created_at = env::block_timestamp();
+        self.posts.insert(&post_id, &post);
+
+        if !was_published {
+            self.index_mentions(&post, &[]);
+        }
+
+        post
+    }
+
+    pub fn schedule_post(&mut self, post_id: u128, publish_at: u64) -> Post {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        if self.is_live(&post) {
+            env::panic_str("ALREADY_PUBLISHED");
+        }
+
+        self.check_post_rate_limit();
+
+        let was_published = post.published;
+
+        // The post counts as created when it goes live
+        post.published = true;
+        post.publish_at = Some(publish_at);
+        post.created_at = publish_at;
+        self.posts.insert(&post_id, &post);
+
+        if !was_published {
+            self.index_mentions(&post, &[]);
+        }
+
+        post
+    }
+
+    // Whether a post has been published and any scheduled time has passed
+    fn is_live(&self, post: &Post) -> bool {
+        post.published
+            && post
+                .publish_at
+                .map_or(true, |publish_at| env::block_timestamp() >= publish_at)
+    }
+
+    pub fn get_my_drafts(&self) -> Vec<Post> {
+        self.posts_by_owner
+            .get(&env::signer_account_id())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| !self.is_live(post) && !post.deleted)
+            .collect()
+    }
+
+    #[payable]
+    pub fn add_posts(&mut self, posts: Vec<AddPostArgs>) -> Vec<Post> {
+        let initial_storage_usage = env::storage_usage();
+
+        if posts.len() > MAX_POSTS_PER_BATCH {
+            env::panic_str("TOO_MANY_POSTS");
+        }
+
+        // A batch counts as a single post towards the rate limit
+        self.check_post_rate_limit();
+
+        // Any invalid post panics, reverting the whole batch
+        let posts: Vec<Post> = posts
+            .into_iter()
+            .map(|args| {
+                let post_storage_usage = env::storage_usage();
+
+                let mut post = self.internal_add_post(
+                    args.title,
+                    args.description,
+                    args.tags,
+                    args.media,
+                    args.media_type,
+                    Visibility::Public,
+                    true,
+                );
+
+                let storage_paid = env::storage_usage().saturating_sub(post_storage_usage);
+                self.record_storage_paid(&mut post, storage_paid);
+                post
+            })
+            .collect();
+
+        pay_for_storage(initial_storage_usage);
+        posts
+    }
+
+    // storage_paid has a fixed size, so recording it after measuring leaves the usage unchanged
+    fn record_storage_paid(&mut self, post: &mut Post, storage_paid: u64) {
+        post.storage_paid = storage_paid;
+        self.posts.insert(&post.id, post);
+    }
+
+    // Reject posting again within the cooldown of the signer's previous post
+    fn check_post_rate_limit(&mut self) {
+        let sender_id = env::signer_account_id();
+        let now = env::block_timestamp();
+
+        if let Some(last_post_at) = self.last_post_at.get(&sender_id) {
+            if now.saturating_sub(last_post_at) < DEFAULT_POST_COOLDOWN {
+                env::panic_str("RATE_LIMITED");
+            }
+        }
+
+        self.last_post_at.insert(&sender_id, &now);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn internal_add_post(
+        &mut self,
+        title: String,
+        description: String,
+        tags: String,
+        media: String,
+        media_type: String,
+        visibility: Visibility,
+        published: bool,
+    ) -> Post {
+        validate_post(&title, &description);
+
+        let mut tags = parse_tags(&tags);
+        let media = parse_media(&media);
+        let media_type = parse_media_type(&media_type);
+
+        // Merge in any hashtags from the description
+        for hashtag in extract_hashtags(&description) {
+            if !tags.contains(&hashtag) {
+                tags.push(hashtag);
+            }
+        }
+
+        validate_tags(&tags);
+
+        let post = self.create_post(
             title,
             description,
-            tags: tags.clone(),
+            tags,
             media,
-            users_who_liked: Vec::<AccountId>::new(),
-            owner_id: env::signer_account_id(),
-        };
+            media_type,
+            visibility,
+            published,
+            None,
+            false,
+        );
+
+        self.add_posts_by_keyword(&post);
+
+        // Drafts mention nobody until they're published
+        if post.published {
+            self.index_mentions(&post, &[]);
+        }
+
+        post
+    }
+
+    // Index the post under each account it mentions
+    // Accounts in already_notified were told about the post before and aren't notified again
+    fn index_mentions(&mut self, post: &Post, already_notified: &[AccountId]) {
+        for account_id in extract_mentions(&post.description) {
+            let mut mentions = self.mentions_by_user.get(&account_id).unwrap_or_default();
+            mentions.push(post.id);
+            self.mentions_by_user.insert(&account_id, &mentions);
+
+            if !already_notified.contains(&account_id) {
+                self.notify(&account_id, NotificationKind::Mention, Some(post.id));
+            }
+        }
+    }
+
+    // Remove the post from the mentions of every account it mentions
+    fn remove_mentions(&mut self, post: &Post) {
+        for account_id in extract_mentions(&post.description) {
+            if let Some(mut mentions) = self.mentions_by_user.get(&account_id) {
+                mentions.retain(|mention_post_id| mention_post_id != &post.id);
+                self.mentions_by_user.insert(&account_id, &mentions);
+            }
+        }
+    }
+
+    // Reposts and quotes can only point at posts anyone can already see
+    fn get_repostable_post(&self, post_id: u128) -> Post {
+        let post = self
+            .posts
+            .get(&post_id)
+            .filter(|post| self.is_listed(post))
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        // Reposting would show the post to accounts that don't follow its author
+        if post.visibility == Visibility::FollowersOnly {
+            env::panic_str("CANNOT_REPOST_FOLLOWERS_ONLY");
+        }
+
+        post
+    }
+
+    #[payable]
+    pub fn repost(&mut self, post_id: u128) -> Post {
+        let initial_storage_usage = env::storage_usage();
+
+        self.check_post_rate_limit();
+
+        let mut original = self.get_repostable_post(post_id);
+
+        original.repost_count += 1;
+        self.posts.insert(&post_id, &original);
+
+        let mut post = self.create_post(
+            original.title,
+            original.description,
+            original.tags,
+            original.media,
+            original.media_type,
+            Visibility::Public,
+            true,
+            Some(post_id),
+            false,
+        );
+
+        let storage_paid = pay_for_storage(initial_storage_usage);
+        self.record_storage_paid(&mut post, storage_paid);
+        post
+    }
+
+    #[payable]
+    pub fn quote_post(&mut self, post_id: u128, commentary: String) -> Post {
+        let initial_storage_usage = env::storage_usage();
+
+        self.check_post_rate_limit();
+
+        let original = self.get_repostable_post(post_id);
+
+        validate_post(&original.title, &commentary);
+
+        let tags = extract_hashtags(&commentary);
+        validate_tags(&tags);
+        let mut post = self.create_post(
+            original.title,
+            commentary,
+            tags,
+            Vec::<String>::new(),
+            MediaType::Text,
+            Visibility::Public,
+            true,
+            Some(post_id),
+            true,
+        );
+
+        self.add_posts_by_keyword(&post);
+        self.index_mentions(&post, &[]);
+
+        let mut quotes = self.quotes_by_post.get(&post_id).unwrap_or_default();
+        quotes.push(post.id);
+        self.quotes_by_post.insert(&post_id, &quotes);
+
+        let storage_paid = pay_for_storage(initial_storage_usage);
+        self.record_storage_paid(&mut post, storage_paid);
+        post
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_post(
+        &mut self,
+        title: String,
+        description: String,
+        tags: Vec<String>,
+        media: Vec<String>,
+        media_type: MediaType,
+        visibility: Visibility,
+        published: bool,
+        reposted_from: Option<u128>,
+        quote: bool,
+    ) -> Post {
+        let post = Post {
+            id: self.number_of_posts,
+            title,
+            description,
+            tags: tags.clone(),
+            media,
+            media_type,
+            users_who_liked: Vec::<AccountId>::new(),
+            like_count: 0,
+            owner_id: env::signer_account_id(),
+            created_at: env::block_timestamp(),
+            reposted_from,
+            view_count: 0,
+            total_tips: 0,
+            edit_history: Vec::<(u64, String)>::new(),
+            deleted: false,
+            visibility,
+            published,
+            publish_at: None,
+            auto_hidden: false,
+            comment_policy: CommentPolicy::Anyone,
+            repost_count: 0,
+            quote,
+            storage_payer: env::predecessor_account_id(),
+            storage_paid: 0,
+        };
+
+        self.number_of_posts += 1;
+        self.posts.insert(&post.id, &post);
+
+        // Index the post id under its owner
+        let mut owner_posts = self.posts_by_owner.get(&post.owner_id).unwrap_or_default();
+        owner_posts.push(post.id);
+        self.posts_by_owner.insert(&post.owner_id, &owner_posts);
+
+        // Fan the post out to the timeline of every follower
+        for follower_id in self.followers.get(&post.owner_id).unwrap_or_default() {
+            let mut timeline = self.timeline.get(&follower_id).unwrap_or_default();
+            timeline.push(post.id);
+            self.timeline.insert(&follower_id, &timeline);
+        }
+
+        self.add_posts_by_tag(post.id, tags);
+
+        log_event(
+            "post_created",
+            json!({ "post_id": post.id, "owner_id": post.owner_id }),
+        );
+
+        post
+    }
+
+    pub fn update_post_tags(&mut self, post_id: u128, tags: String) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        let tags = parse_tags(&tags);
+        validate_tags(&tags);
+
+        self.remove_posts_by_tag(post_id, &post.tags);
+
+        post.tags = tags;
+        self.posts.insert(&post_id, &post);
+
+        self.add_posts_by_tag(post_id, post.tags);
+    }
+
+    pub fn remove_tag_from_post(&mut self, post_id: u128, tag: String) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        let tag = normalize_tag(&tag);
+
+        if !post.tags.contains(&tag) {
+            return;
+        }
+
+        post.tags.retain(|post_tag| post_tag != &tag);
+        self.posts.insert(&post_id, &post);
+
+        self.remove_posts_by_tag(post_id, &[tag]);
+    }
+
+    pub fn add_tag_to_post(&mut self, post_id: u128, tag: String) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        let tag = normalize_tag(&tag);
+
+        if tag.is_empty() {
+            env::panic_str("TAG_REQUIRED");
+        }
+
+        if post.tags.contains(&tag) {
+            return;
+        }
+
+        if post.tags.len() >= MAX_TAGS_PER_POST {
+            env::panic_str("TOO_MANY_TAGS");
+        }
+
+        post.tags.push(tag.clone());
+        self.posts.insert(&post_id, &post);
+
+        self.add_posts_by_tag(post_id, vec![tag]);
+    }
+
+    // Remove a post from each of the given tags, dropping tags left without posts
+    fn remove_posts_by_tag(&mut self, post_id: u128, tags: &[String]) {
+        for tag in tags.iter() {
+            if let Some(mut posts_for_tag) = self.posts_by_tag.get(tag) {
+                posts_for_tag.retain(|tagged_post_id| tagged_post_id != &post_id);
+
+                if posts_for_tag.is_empty() {
+                    self.posts_by_tag.remove(tag);
+                } else {
+                    self.posts_by_tag.insert(tag, &posts_for_tag);
+                }
+            }
+        }
+    }
+
+    // Index a post under every keyword in its title and description
+    fn add_posts_by_keyword(&mut self, post: &Post) {
+        let text = format!("{} {}", post.title, post.description);
+
+        for keyword in extract_keywords(&text) {
+            let mut posts_for_keyword = self.posts_by_keyword.get(&keyword).unwrap_or_default();
+            posts_for_keyword.push(post.id);
+            self.posts_by_keyword.insert(&keyword, &posts_for_keyword);
+        }
+    }
+
+    // Remove a post from its keyword buckets, dropping buckets left without posts
+    fn remove_posts_by_keyword(&mut self, post: &Post) {
+        let text = format!("{} {}", post.title, post.description);
+
+        for keyword in extract_keywords(&text) {
+            if let Some(mut posts_for_keyword) = self.posts_by_keyword.get(&keyword) {
+                posts_for_keyword.retain(|keyword_post_id| keyword_post_id != &post.id);
+
+                if posts_for_keyword.is_empty() {
+                    self.posts_by_keyword.remove(&keyword);
+                } else {
+                    self.posts_by_keyword.insert(&keyword, &posts_for_keyword);
+                }
+            }
+        }
+    }
+
+    #[private]
+    fn add_posts_by_tag(&mut self, post_id: u128, tags: Vec<String>) {
+        let mut posts_for_tag: Vec<u128>;
+
+        for tag in tags {
+            if let None = self.posts_by_tag.get(&tag) {
+                posts_for_tag = Vec::<u128>::new();
+            } else {
+                posts_for_tag = self
+                    .posts_by_tag
+                    .get(&tag)
+                    .unwrap_or_else(|| env::panic_str("NO_POSTS_FOUND"));
+            }
+
+            posts_for_tag.push(post_id);
+            self.posts_by_tag.insert(&tag, &posts_for_tag);
+        }
+    }
+
+    pub fn edit_post(
+        &mut self,
+        post_id: u128,
+        title: String,
+        description: String,
+        media: String,
+    ) -> Post {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        validate_post(&title, &description);
+
+        self.remove_posts_by_keyword(&post);
+
+        // Drafts only have their mentions indexed once published
+        let previous_mentions = extract_mentions(&post.description);
+        if post.published {
+            self.remove_mentions(&post);
+        }
+
+        // Record the previous title, keeping only the most recent edits
+        post.edit_history
+            .push((env::block_timestamp(), post.title.clone()));
+        if post.edit_history.len() > MAX_EDIT_HISTORY {
+            post.edit_history.remove(0);
+        }
+
+        post.title = title;
+        post.description = description;
+        post.media = parse_media(&media);
+
+        self.posts.insert(&post_id, &post);
+        self.add_posts_by_keyword(&post);
+
+        if post.published {
+            self.index_mentions(&post, &previous_mentions);
+        }
+
+        post
+    }
+
+    pub fn get_edit_history(&self, post_id: u128) -> Vec<(u64, String)> {
+        self.posts
+            .get(&post_id)
+            .map(|post| post.edit_history)
+            .unwrap_or_default()
+    }
+
+    pub fn soft_delete_post(&mut self, post_id: u128) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        post.deleted = true;
+        self.posts.insert(&post_id, &post);
+    }
+
+    pub fn delete_post(&mut self, post_id: u128) {
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        let initial_storage_usage = env::storage_usage();
+
+        self.remove_post(&post);
+
+        // Refund whoever paid for the post, but never more than they paid
+        let storage_freed = initial_storage_usage
+            .saturating_sub(env::storage_usage())
+            .min(post.storage_paid);
+        if storage_freed > 0 {
+            Promise::new(post.storage_payer)
+                .transfer(storage_freed as u128 * env::storage_byte_cost());
+        }
+    }
+
+    pub fn admin_remove_post(&mut self, post_id: u128) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic_str("NOT_CONTRACT_OWNER");
+        }
+
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        self.remove_post(&post);
+    }
+
+    // Remove a post along with every index that references it
+    fn remove_post(&mut self, post: &Post) {
+        let post_id = post.id;
+
+        self.posts.remove(&post_id);
+        self.reports.remove(&post_id);
+        self.comments_by_post.remove(&post_id);
+
+        self.quotes_by_post.remove(&post_id);
+        self.pinned_comment.remove(&post_id);
+
+        if let Some(original_id) = post.reposted_from {
+            if post.quote {
+                if let Some(mut quotes) = self.quotes_by_post.get(&original_id) {
+                    quotes.retain(|quote_id| quote_id != &post_id);
+                    self.quotes_by_post.insert(&original_id, &quotes);
+                }
+            } else if let Some(mut original) = self.posts.get(&original_id) {
+                original.repost_count = original.repost_count.saturating_sub(1);
+                self.posts.insert(&original_id, &original);
+            }
+        }
+        self.reactions.remove(&post_id);
+        self.tips_by_post.remove(&post_id);
+
+        // Remove the post from its owners posts
+        if let Some(mut owner_posts) = self.posts_by_owner.get(&post.owner_id) {
+            owner_posts.retain(|owner_post_id| owner_post_id != &post_id);
+            self.posts_by_owner.insert(&post.owner_id, &owner_posts);
+        }
+
+        self.remove_posts_by_tag(post_id, &post.tags);
+        self.remove_posts_by_keyword(post);
+
+        self.remove_mentions(post);
+
+        // Remove the post from the likes of every user who liked it
+        for account_id in post.users_who_liked.iter() {
+            if let Some(mut users_likes) = self.likes_by_user_id.get(account_id) {
+                users_likes.retain(|liked_post_id| liked_post_id != &post_id);
+                self.likes_by_user_id.insert(account_id, &users_likes);
+            }
+        }
+    }
+
+    // Whether a post should show up in public listings
+    fn is_listed(&self, post: &Post) -> bool {
+        self.is_live(post)
+            && !post.deleted
+            && !post.auto_hidden
+            && !self.is_deactivated(&post.owner_id)
+    }
+
+    // Whether a post is listed and visible to every account, used by listings without a viewer
+    fn is_public(&self, post: &Post) -> bool {
+        self.is_listed(post) && post.visibility == Visibility::Public
+    }
+
+    pub fn get_all_posts(&self, from_index: u64, limit: u64) -> Vec<(u128, Post)> {
+        self.posts
+            .iter()
+            .filter(|(_, post)| self.is_public(post))
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    // Posts are returned in ascending id order starting after last_id. Ids are never reused,
+    // so a cursor isn't affected by posts being added or removed between calls
+    pub fn get_posts_from_key(&self, last_id: Option<u128>, limit: u64) -> Vec<(u128, Post)> {
+        let start = match last_id {
+            None => 0,
+            // No post can come after the largest possible id
+            Some(last_id) => match last_id.checked_add(1) {
+                Some(start) => start,
+                None => return Vec::new(),
+            },
+        };
+
+        (start..self.number_of_posts)
+            .filter_map(|post_id| self.posts.get(&post_id).map(|post| (post_id, post)))
+            .filter(|(_, post)| self.is_public(post))
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn get_posts_count(&self) -> u128 {
+        self.number_of_posts
+    }
+
+    pub fn get_post_by_id(&self, post_id: u128, viewer: AccountId) -> Option<Post> {
+        self.posts
+            .get(&post_id)
+            .filter(|post| self.can_view(&viewer, post))
+    }
+
+    // Followers only posts are visible to their author and the author's followers
+    fn can_view(&self, viewer: &AccountId, post: &Post) -> bool {
+        // Drafts and scheduled posts are only visible to their owner
+        if !self.is_live(post) {
+            return viewer == &post.owner_id;
+        }
+
+        match post.visibility {
+            Visibility::Public => true,
+            Visibility::FollowersOnly => {
+                viewer == &post.owner_id || self.is_following(viewer.clone(), post.owner_id.clone())
+            }
+        }
+    }
+
+    // Listed posts the signer can see, for actions that would otherwise reveal hidden ones
+    fn get_viewable_post(&self, post_id: u128) -> Option<Post> {
+        let viewer = env::signer_account_id();
+
+        self.posts
+            .get(&post_id)
+            .filter(|post| self.is_listed(post) && self.can_view(&viewer, post))
+    }
+
+    // Every listed post by owner_id, in the order they were created
+    fn owner_posts(&self, owner_id: &AccountId) -> Vec<Post> {
+        self.posts_by_owner
+            .get(owner_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_public(post))
+            .collect()
+    }
+
+    pub fn get_posts_by_owner(
+        &self,
+        owner_id: AccountId,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<Post> {
+        let mut posts = self.owner_posts(&owner_id);
+
+        // Newest posts first, ties broken by the most recent id
+        posts.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+        posts
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn like_a_post(&mut self, post_id: u128) -> Option<Post> {
+        // Copy and update post
+        let mut post_copy = self.get_viewable_post(post_id)?;
+
+        // Don't allow the same user to like a post more than once
+        if post_copy
+            .users_who_liked
+            .contains(&env::signer_account_id())
+        {
+            return Some(post_copy);
+        }
+
+        // Update the post copy
+        post_copy.users_who_liked.push(env::signer_account_id());
+        post_copy.like_count += 1;
+
+        // Update the posts state
+        self.posts.insert(&post_id, &post_copy.clone());
+
+        self.add_post_to_my_liked(env::signer_account_id(), &post_copy);
+
+        self.notify(&post_copy.owner_id, NotificationKind::Like, Some(post_id));
+
+        log_event(
+            "post_liked",
+            json!({ "post_id": post_id, "liker": env::signer_account_id() }),
+        );
+
+        Some(post_copy)
+    }
+
+    #[private]
+    pub fn add_post_to_my_liked(&mut self, sender_id: AccountId, post: &Post) {
+        // Find the users liked posts
+        let users_likes = self.likes_by_user_id.get(&sender_id);
+
+        // Add post to users likes
+        if let None = users_likes {
+            // Create users likes
+            self.likes_by_user_id.insert(&sender_id, &vec![post.id]);
+        } else {
+            // Update users likes
+            let mut checked_users_likes =
+                users_likes.unwrap_or_else(|| env::panic_str("UNABLE_TO_FIND_USERS_LIKES"));
+
+            checked_users_likes.push(post.id);
+
+            self.likes_by_user_id
+                .insert(&sender_id, &checked_users_likes);
+        }
+    }
+
+    pub fn unlike_a_post(&mut self, post_id: u128) -> Post {
+        let sender_id = env::signer_account_id();
+
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if !post.users_who_liked.contains(&sender_id) {
+            env::panic_str("POST_NOT_LIKED");
+        }
+
+        // Remove the user from the post's likes
+        post.users_who_liked
+            .retain(|account_id| account_id != &sender_id);
+        post.like_count -= 1;
+        self.posts.insert(&post_id, &post);
+
+        // Remove the post from the users liked posts
+        if let Some(mut users_likes) = self.likes_by_user_id.get(&sender_id) {
+            users_likes.retain(|liked_post_id| liked_post_id != &post_id);
+            self.likes_by_user_id.insert(&sender_id, &users_likes);
+        }
+
+        post
+    }
+
+    pub fn toggle_like(&mut self, post_id: u128) -> bool {
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if post.users_who_liked.contains(&env::signer_account_id()) {
+            self.unlike_a_post(post_id);
+            false
+        } else {
+            // The post exists but the signer can't see it
+            if self.like_a_post(post_id).is_none() {
+                env::panic_str("POST_NOT_FOUND");
+            }
+            true
+        }
+    }
+
+    pub fn get_post_likers(&self, post_id: u128, from_index: u64, limit: u64) -> Vec<AccountId> {
+        self.posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"))
+            .users_who_liked
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn has_liked(&self, post_id: u128, account_id: AccountId) -> bool {
+        self.posts
+            .get(&post_id)
+            .map_or(false, |post| post.users_who_liked.contains(&account_id))
+    }
+
+    pub fn get_liked_posts(&self) -> Vec<Post> {
+        let viewer = env::signer_account_id();
+
+        self.liked_posts(&viewer)
+            .into_iter()
+            .filter(|post| self.can_view(&viewer, post))
+            .collect()
+    }
+
+    pub fn get_liked_posts_for_account(&self, account_id: AccountId) -> Vec<Post> {
+        self.liked_posts(&account_id)
+            .into_iter()
+            .filter(|post| self.is_public(post))
+            .collect()
+    }
+
+    // Every listed post account_id has liked, in the order they were liked
+    fn liked_posts(&self, account_id: &AccountId) -> Vec<Post> {
+        self.likes_by_user_id
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_listed(post))
+            .collect()
+    }
+
+    // Posts are returned in the order they were tagged
+    pub fn get_posts_by_tag(&self, tag: String, from_index: u64, limit: u64) -> Vec<Post> {
+        self.posts_by_tag
+            .get(&normalize_tag(&tag))
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_public(post))
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    // Counts the same posts get_posts_by_tag pages through
+    pub fn get_post_count_by_tag(&self, tag: String) -> u64 {
+        self.public_post_count(
+            &self
+                .posts_by_tag
+                .get(&normalize_tag(&tag))
+                .unwrap_or_default(),
+        )
+    }
+
+    fn public_post_count(&self, post_ids: &[u128]) -> u64 {
+        post_ids
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_public(post))
+            .count() as u64
+    }
+
+    // Tags with at least one public post, alongside how many they have
+    fn public_tag_counts(&self) -> Vec<(String, u64)> {
+        self.posts_by_tag
+            .iter()
+            .map(|(tag, post_ids)| (tag, self.public_post_count(&post_ids)))
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+
+    pub fn set_comments_enabled(&mut self, post_id: u128, enabled: bool) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        // Disabling comments is the same as a Nobody comment policy
+        post.comment_policy = if enabled {
+            CommentPolicy::Anyone
+        } else {
+            CommentPolicy::Nobody
+        };
+        self.posts.insert(&post_id, &post);
+    }
+
+    pub fn set_comment_policy(&mut self, post_id: u128, comment_policy: CommentPolicy) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        post.comment_policy = comment_policy;
+        self.posts.insert(&post_id, &post);
+    }
+
+    pub fn add_comment(
+        &mut self,
+        post_id: u128,
+        text: String,
+        parent_comment_id: Option<u128>,
+    ) -> Comment {
+        if text.trim().is_empty() {
+            env::panic_str("COMMENT_REQUIRED");
+        }
+        if text.chars().count() > MAX_COMMENT_LENGTH {
+            env::panic_str("COMMENT_TOO_LONG");
+        }
+
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        let sender_id = env::signer_account_id();
+
+        match post.comment_policy {
+            CommentPolicy::Anyone => {}
+            CommentPolicy::FollowersOnly => {
+                // The author can always comment on their own post
+                let is_follower = self.is_following(sender_id.clone(), post.owner_id.clone());
+
+                if sender_id != post.owner_id && !is_follower {
+                    env::panic_str("NOT_A_FOLLOWER");
+                }
+            }
+            CommentPolicy::Nobody => env::panic_str("COMMENTS_DISABLED"),
+        }
+
+        let mut comments = self.comments_by_post.get(&post_id).unwrap_or_default();
+
+        // Replies must point at a comment on the same post
+        if let Some(parent_id) = parent_comment_id {
+            if !comments.iter().any(|comment| comment.id == parent_id) {
+                env::panic_str("PARENT_COMMENT_NOT_FOUND");
+            }
+        }
+
+        let comment = Comment {
+            id: self.number_of_comments,
+            post_id,
+            author: sender_id,
+            text,
+            created_at: env::block_timestamp(),
+            parent_comment_id,
+            liked_by: Vec::<AccountId>::new(),
+        };
+
+        self.number_of_comments += 1;
+
+        comments.push(comment.clone());
+        self.comments_by_post.insert(&post_id, &comments);
+
+        comment
+    }
+
+    pub fn get_comments(&self, post_id: u128) -> Vec<Comment> {
+        self.comments_by_post.get(&post_id).unwrap_or_default()
+    }
+
+    pub fn get_comment_count(&self, post_id: u128) -> u64 {
+        self.comments_by_post
+            .get(&post_id)
+            .map_or(0, |comments| comments.len() as u64)
+    }
+
+    pub fn like_comment(&mut self, post_id: u128, comment_id: u128) {
+        let sender_id = env::signer_account_id();
+        let mut comments = self.get_comments(post_id);
+
+        let comment = comments
+            .iter_mut()
+            .find(|comment| comment.id == comment_id)
+            .unwrap_or_else(|| env::panic_str("COMMENT_NOT_FOUND"));
+
+        // Don't allow the same user to like a comment more than once
+        if comment.liked_by.contains(&sender_id) {
+            return;
+        }
+
+        comment.liked_by.push(sender_id);
+        self.comments_by_post.insert(&post_id, &comments);
+    }
+
+    pub fn delete_comment(&mut self, post_id: u128, comment_id: u128) {
+        let mut comments = self.get_comments(post_id);
+
+        let comment = comments
+            .iter()
+            .find(|comment| comment.id == comment_id)
+            .unwrap_or_else(|| env::panic_str("COMMENT_NOT_FOUND"));
+
+        if env::signer_account_id() != comment.author {
+            env::panic_str("NOT_COMMENT_AUTHOR");
+        }
+
+        comments.retain(|comment| comment.id != comment_id);
+        self.comments_by_post.insert(&post_id, &comments);
+    }
+
+    pub fn pin_comment(&mut self, post_id: u128, comment_id: u128) {
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        if !self
+            .get_comments(post_id)
+            .iter()
+            .any(|comment| comment.id == comment_id)
+        {
+            env::panic_str("COMMENT_NOT_FOUND");
+        }
+
+        self.pinned_comment.insert(&post_id, &comment_id);
+    }
+
+    pub fn get_pinned_comment(&self, post_id: u128) -> Option<Comment> {
+        let comment_id = self.pinned_comment.get(&post_id)?;
+
+        self.get_comments(post_id)
+            .into_iter()
+            .find(|comment| comment.id == comment_id)
+    }
+
+    pub fn get_comment_like_count(&self, post_id: u128, comment_id: u128) -> u64 {
+        self.get_comments(post_id)
+            .iter()
+            .find(|comment| comment.id == comment_id)
+            .map_or(0, |comment| comment.liked_by.len() as u64)
+    }
+
+    pub fn get_replies(&self, post_id: u128, comment_id: u128) -> Vec<Comment> {
+        self.get_comments(post_id)
+            .into_iter()
+            .filter(|comment| comment.parent_comment_id == Some(comment_id))
+            .collect()
+    }
+
+    pub fn follow(&mut self, account_id: AccountId) {
+        let sender_id = env::signer_account_id();
+
+        if sender_id == account_id {
+            env::panic_str("CANNOT_FOLLOW_SELF");
+        }
+
+        let mut following = self.following.get(&sender_id).unwrap_or_default();
+
+        // Following an account twice is a no-op
+        if following.contains(&account_id) {
+            return;
+        }
+
+        following.push(account_id.clone());
+        self.following.insert(&sender_id, &following);
+
+        // Backfill the timeline with the followed account's most recent posts, bounded since
+        // following isn't paid for
+        let owner_posts = self.posts_by_owner.get(&account_id).unwrap_or_default();
+        let mut timeline = self.timeline.get(&sender_id).unwrap_or_default();
+        for post_id in owner_posts.iter().rev().take(MAX_TIMELINE_BACKFILL) {
+            if !timeline.contains(post_id) {
+                timeline.push(*post_id);
+            }
+        }
+        self.timeline.insert(&sender_id, &timeline);
+
+        let mut followers = self.followers.get(&account_id).unwrap_or_default();
+        followers.push(sender_id);
+        self.followers.insert(&account_id, &followers);
+
+        self.notify(&account_id, NotificationKind::Follow, None);
+    }
+
+    pub fn unfollow(&mut self, account_id: AccountId) {
+        let sender_id = env::signer_account_id();
+
+        if let Some(mut following) = self.following.get(&sender_id) {
+            following.retain(|followed_id| followed_id != &account_id);
+            self.following.insert(&sender_id, &following);
+        }
+
+        if let Some(mut followers) = self.followers.get(&account_id) {
+            followers.retain(|follower_id| follower_id != &sender_id);
+            self.followers.insert(&account_id, &followers);
+        }
+    }
+
+    pub fn get_following(&self, account_id: AccountId) -> Vec<AccountId> {
+        self.following.get(&account_id).unwrap_or_default()
+    }
+
+    pub fn is_following(&self, follower: AccountId, followee: AccountId) -> bool {
+        self.following
+            .get(&follower)
+            .map_or(false, |following| following.contains(&followee))
+    }
+
+    pub fn get_mutual_follows(&self, a: AccountId, b: AccountId) -> Vec<AccountId> {
+        let b_following = self.get_following(b);
+
+        let mut mutual_follows: Vec<AccountId> = self
+            .get_following(a)
+            .into_iter()
+            .filter(|account_id| b_following.contains(account_id))
+            .collect();
+
+        mutual_follows.sort();
+        mutual_follows
+    }
+
+    // Followers are returned in the order they followed account_id
+    pub fn get_followers(
+        &self,
+        account_id: AccountId,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<AccountId> {
+        self.followers
+            .get(&account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn get_follower_count(&self, account_id: AccountId) -> u64 {
+        self.followers
+            .get(&account_id)
+            .map_or(0, |followers| followers.len() as u64)
+    }
+
+    // Every post in the precomputed timeline of account_id, oldest first. Reposts of the same
+    // post collapse into the earliest one, the original's repost_count has the total.
+    // Timelines aren't pruned on unfollow, posts already fanned out stay in the feed
+    fn feed_posts(&self, account_id: &AccountId) -> Vec<Post> {
+        let mut posts: Vec<Post> = self
+            .timeline
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_listed(post))
+            .filter(|post| !self.is_hidden(account_id, &post.owner_id))
+            .filter(|post| self.can_view(account_id, post))
+            .collect();
+
+        posts.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+        let mut reposted_ids = Vec::<u128>::new();
+        posts.retain(|post| match post.reposted_from {
+            // Quotes add their own commentary so they're never collapsed
+            Some(_) if post.quote => true,
+            Some(original_id) if reposted_ids.contains(&original_id) => false,
+            Some(original_id) => {
+                reposted_ids.push(original_id);
+                true
+            }
+            None => true,
+        });
+
+        posts
+    }
+
+    pub fn get_feed(&self, account_id: AccountId, from_index: u64, limit: u64) -> Vec<Post> {
+        let mut feed = self.feed_posts(&account_id);
+
+        // Newest posts first
+        feed.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+        feed.into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn get_feed_count(&self, account_id: AccountId) -> u64 {
+        self.feed_posts(&account_id).len() as u64
+    }
+
+    // Linear scan over every post, only intended for modest datasets
+    pub fn search_posts_by_title(&self, query: String, from_index: u64, limit: u64) -> Vec<Post> {
+        let query = query.to_lowercase();
+
+        self.posts
+            .values()
+            .filter(|post| self.is_public(post))
+            .filter(|post| post.title.to_lowercase().contains(&query))
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn bookmark_post(&mut self, post_id: u128) {
+        if self.get_viewable_post(post_id).is_none() {
+            env::panic_str("POST_NOT_FOUND");
+        }
+
+        let sender_id = env::signer_account_id();
+        let mut bookmarks = self.bookmarks_by_user.get(&sender_id).unwrap_or_default();
+
+        // Bookmarking a post twice is a no-op
+        if bookmarks.contains(&post_id) {
+            return;
+        }
+
+        bookmarks.push(post_id);
+        self.bookmarks_by_user.insert(&sender_id, &bookmarks);
+    }
+
+    pub fn remove_bookmark(&mut self, post_id: u128) {
+        let sender_id = env::signer_account_id();
+
+        if let Some(mut bookmarks) = self.bookmarks_by_user.get(&sender_id) {
+            bookmarks.retain(|bookmarked_id| bookmarked_id != &post_id);
+            self.bookmarks_by_user.insert(&sender_id, &bookmarks);
+        }
+    }
+
+    pub fn get_bookmarks(&self) -> Vec<Post> {
+        let viewer = env::signer_account_id();
+
+        self.bookmarks_by_user
+            .get(&viewer)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_listed(post))
+            .filter(|post| self.can_view(&viewer, post))
+            .collect()
+    }
+
+    pub fn increment_view_count(&mut self, post_id: u128) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        // Owners viewing their own posts don't count
+        if env::signer_account_id() == post.owner_id {
+            return;
+        }
+
+        post.view_count += 1;
+        self.posts.insert(&post_id, &post);
+    }
+
+    // Linear scan over every post, only intended for modest datasets
+    pub fn get_trending_posts(&self, limit: u64) -> Vec<Post> {
+        let mut posts: Vec<Post> = self
+            .posts
+            .values()
+            .filter(|post| self.is_public(post))
+            .collect();
+
+        // Most liked first, then newest, then lowest id
+        posts.sort_by(|a, b| {
+            b.like_count
+                .cmp(&a.like_count)
+                .then(b.created_at.cmp(&a.created_at))
+                .then(a.id.cmp(&b.id))
+        });
+
+        posts.into_iter().take(limit as usize).collect()
+    }
+
+    pub fn get_top_tipped_posts(&self, limit: u64) -> Vec<Post> {
+        let mut posts: Vec<Post> = self
+            .posts
+            .values()
+            .filter(|post| self.is_public(post))
+            .collect();
+
+        // Most tipped first, ties broken by the newest post
+        posts.sort_by(|a, b| {
+            b.total_tips
+                .cmp(&a.total_tips)
+                .then(b.created_at.cmp(&a.created_at))
+                .then(b.id.cmp(&a.id))
+        });
+
+        posts.into_iter().take(limit as usize).collect()
+    }
+
+    pub fn block_user(&mut self, account_id: AccountId) {
+        let sender_id = env::signer_account_id();
+
+        if sender_id == account_id {
+            env::panic_str("CANNOT_BLOCK_SELF");
+        }
+
+        let mut blocked = self.blocked_by_user.get(&sender_id).unwrap_or_default();
+
+        if !blocked.contains(&account_id) {
+            blocked.push(account_id);
+            self.blocked_by_user.insert(&sender_id, &blocked);
+        }
+    }
+
+    pub fn unblock_user(&mut self, account_id: AccountId) {
+        let sender_id = env::signer_account_id();
+
+        if let Some(mut blocked) = self.blocked_by_user.get(&sender_id) {
+            blocked.retain(|blocked_id| blocked_id != &account_id);
+            self.blocked_by_user.insert(&sender_id, &blocked);
+        }
+    }
+
+    pub fn get_blocked(&self) -> Vec<AccountId> {
+        self.blocked_by_user
+            .get(&env::signer_account_id())
+            .unwrap_or_default()
+    }
+
+    // Whether a viewer has chosen not to see posts from an author
+    fn is_hidden(&self, viewer: &AccountId, author: &AccountId) -> bool {
+        self.blocked_by_user
+            .get(viewer)
+            .map_or(false, |blocked_ids| blocked_ids.contains(author))
+    }
+
+    pub fn is_blocked(&self, blocker: AccountId, blocked: AccountId) -> bool {
+        self.blocked_by_user
+            .get(&blocker)
+            .map_or(false, |blocked_ids| blocked_ids.contains(&blocked))
+    }
+
+    #[payable]
+    pub fn tip_post(&mut self, post_id: u128) {
+        let amount = env::attached_deposit();
+
+        if amount == 0 {
+            env::panic_str("TIP_REQUIRED");
+        }
+
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() == post.owner_id {
+            env::panic_str("CANNOT_TIP_OWN_POST");
+        }
+
+        post.total_tips += amount;
+        self.posts.insert(&post_id, &post);
+
+        // Repeat tips from the same account are summed into a single entry
+        let tipper_id = env::signer_account_id();
+        let mut tips = self.tips_by_post.get(&post_id).unwrap_or_default();
+        match tips
+            .iter_mut()
+            .find(|(account_id, _)| account_id == &tipper_id)
+        {
+            Some((_, total)) => *total += amount,
+            None => tips.push((tipper_id, amount)),
+        }
+        self.tips_by_post.insert(&post_id, &tips);
+
+        Promise::new(post.owner_id).transfer(amount);
+    }
+
+    pub fn get_tippers(&self, post_id: u128) -> Vec<(AccountId, u128)> {
+        self.tips_by_post.get(&post_id).unwrap_or_default()
+    }
+
+    pub fn get_posts_by_tags(&self, tags: Vec<String>, match_all: bool) -> Vec<Post> {
+        let tags = parse_tags(&tags.join(","));
+
+        let posts_for_tags: Vec<Vec<u128>> = tags
+            .iter()
+            .map(|tag| self.posts_by_tag.get(tag).unwrap_or_default())
+            .collect();
+
+        let mut post_ids = Vec::<u128>::new();
+
+        for post_id in posts_for_tags.iter().flatten() {
+            if post_ids.contains(post_id) {
+                continue;
+            }
+
+            // Match all keeps only posts found under every tag
+            if match_all
+                && !posts_for_tags
+                    .iter()
+                    .all(|tagged_post_ids| tagged_post_ids.contains(post_id))
+            {
+                continue;
+            }
+
+            post_ids.push(*post_id);
+        }
+
+        post_ids
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_public(post))
+            .collect()
+    }
+
+    pub fn get_all_tags(&self) -> Vec<String> {
+        self.public_tag_counts()
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .collect()
+    }
+
+    pub fn get_tag_counts(&self) -> Vec<(String, u64)> {
+        let mut tag_counts = self.public_tag_counts();
+
+        // Most used tags first, then alphabetical
+        tag_counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        tag_counts
+    }
+
+    pub fn set_profile(&mut self, display_name: String, bio: String, avatar_url: String) {
+        if display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+            env::panic_str("DISPLAY_NAME_TOO_LONG");
+        }
+        if bio.chars().count() > MAX_BIO_LENGTH {
+            env::panic_str("BIO_TOO_LONG");
+        }
+        if avatar_url.chars().count() > MAX_AVATAR_URL_LENGTH {
+            env::panic_str("AVATAR_URL_TOO_LONG");
+        }
+
+        let profile = Profile {
+            display_name,
+            bio,
+            avatar_url,
+        };
+
+        self.profiles.insert(&env::signer_account_id(), &profile);
+    }
+
+    pub fn get_profile(&self, account_id: AccountId) -> Option<Profile> {
+        if self.is_deactivated(&account_id) {
+            return None;
+        }
+
+        self.profiles.get(&account_id)
+    }
+
+    pub fn pin_post(&mut self, post_id: u128) {
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        self.pinned_post.insert(&post.owner_id, &post_id);
+    }
+
+    pub fn unpin_post(&mut self) {
+        self.pinned_post.remove(&env::signer_account_id());
+    }
+
+    pub fn get_pinned_post(&self, account_id: AccountId) -> Option<Post> {
+        self.pinned_post
+            .get(&account_id)
+            .and_then(|post_id| self.posts.get(&post_id))
+            .filter(|post| self.is_public(post))
+    }
+
+    pub fn report_post(&mut self, post_id: u128, reason: String) {
+        if reason.chars().count() > MAX_REPORT_REASON_LENGTH {
+            env::panic_str("REASON_TOO_LONG");
+        }
+
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        let reporter_id = env::signer_account_id();
+        let mut reports = self.reports.get(&post_id).unwrap_or_default();
+
+        if reports
+            .iter()
+            .any(|(account_id, _)| account_id == &reporter_id)
+        {
+            env::panic_str("ALREADY_REPORTED");
+        }
+
+        reports.push((reporter_id, reason));
+        self.reports.insert(&post_id, &reports);
+
+        // Hide the post from listings until the contract owner reviews it
+        if !post.auto_hidden && reports.len() as u64 >= self.report_threshold {
+            post.auto_hidden = true;
+            self.posts.insert(&post_id, &post);
+        }
+    }
+
+    pub fn clear_reports(&mut self, post_id: u128) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic_str("NOT_CONTRACT_OWNER");
+        }
+
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        self.reports.remove(&post_id);
+
+        post.auto_hidden = false;
+        self.posts.insert(&post_id, &post);
+    }
+
+    pub fn get_reports(&self, post_id: u128) -> Vec<(AccountId, String)> {
+        self.reports.get(&post_id).unwrap_or_default()
+    }
+
+    pub fn get_recent_posts(&self, viewer: AccountId, from_index: u64, limit: u64) -> Vec<Post> {
+        let mut posts: Vec<Post> = self
+            .posts
+            .values()
+            .filter(|post| self.is_listed(post))
+            .filter(|post| !self.is_hidden(&viewer, &post.owner_id))
+            .filter(|post| self.can_view(&viewer, post))
+            .collect();
+
+        // Newest posts first, ties broken by the most recent id
+        posts.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+        posts
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn get_mentions(&self, account_id: AccountId) -> Vec<Post> {
+        self.mentions_by_user
+            .get(&account_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_public(post))
+            .collect()
+    }
+
+    // Add a notification from the signer to the inbox of account_id
+    fn notify(&mut self, account_id: &AccountId, kind: NotificationKind, post_id: Option<u128>) {
+        let actor = env::signer_account_id();
+
+        // Users aren't notified about their own actions
+        if &actor == account_id {
+            return;
+        }
+
+        let mut notifications = self.notifications.get(account_id).unwrap_or_default();
+
+        // Liking the same post again shortly after only notifies once
+        if kind == NotificationKind::Like {
+            let last_like = notifications.iter().rev().find(|notification| {
+                notification.kind == NotificationKind::Like
+                    && notification.actor == actor
+                    && notification.post_id == post_id
+            });
+
+            if let Some(last_like) = last_like {
+                if env::block_timestamp().saturating_sub(last_like.created_at)
+                    < self.like_notification_window
+                {
+                    return;
+                }
+            }
+        }
+
+        notifications.push(Notification {
+            kind,
+            actor,
+            post_id,
+            created_at: env::block_timestamp(),
+            read: false,
+        });
+        self.notifications.insert(account_id, &notifications);
+    }
+
+    pub fn get_notifications(&self, from_index: u64, limit: u64) -> Vec<Notification> {
+        self.notifications
+            .get(&env::signer_account_id())
+            .unwrap_or_default()
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn clear_notifications(&mut self) {
+        self.notifications.remove(&env::signer_account_id());
+    }
+
+    pub fn mark_notifications_read(&mut self) {
+        let sender_id = env::signer_account_id();
+
+        if let Some(mut notifications) = self.notifications.get(&sender_id) {
+            for notification in notifications.iter_mut() {
+                notification.read = true;
+            }
+            self.notifications.insert(&sender_id, &notifications);
+        }
+    }
+
+    pub fn get_unread_count(&self) -> u64 {
+        self.notifications
+            .get(&env::signer_account_id())
+            .unwrap_or_default()
+            .iter()
+            .filter(|notification| !notification.read)
+            .count() as u64
+    }
+
+    // Linear scan over every post, start and end are both inclusive
+    pub fn get_posts_created_between(&self, start: u64, end: u64) -> Vec<Post> {
+        if start > end {
+            env::panic_str("INVALID_RANGE");
+        }
+
+        self.posts
+            .values()
+            .filter(|post| self.is_public(post))
+            .filter(|post| post.created_at >= start && post.created_at <= end)
+            .collect()
+    }
+
+    pub fn react(&mut self, post_id: u128, reaction: Reaction) {
+        if self.posts.get(&post_id).is_none() {
+            env::panic_str("POST_NOT_FOUND");
+        }
+
+        let sender_id = env::signer_account_id();
+        let mut reactions = self.reactions.get(&post_id).unwrap_or_default();
+
+        // Each user has a single reaction, reacting again replaces it
+        reactions.retain(|(account_id, _)| account_id != &sender_id);
+        reactions.push((sender_id, reaction));
+        self.reactions.insert(&post_id, &reactions);
+    }
+
+    pub fn get_reaction_counts(&self, post_id: u128) -> Vec<(Reaction, u64)> {
+        let reactions = self.reactions.get(&post_id).unwrap_or_default();
+
+        [
+            Reaction::Like,
+            Reaction::Love,
+            Reaction::Laugh,
+            Reaction::Angry,
+        ]
+        .into_iter()
+        .map(|kind| {
+            let count = reactions
+                .iter()
+                .filter(|(_, reaction)| reaction == &kind)
+                .count() as u64;
+            (kind, count)
+        })
+        .collect()
+    }
+
+    pub fn transfer_ownership(&mut self, new_owner: AccountId) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic_str("NOT_CONTRACT_OWNER");
+        }
+
+        if new_owner == self.owner_id {
+            env::panic_str("ALREADY_CONTRACT_OWNER");
+        }
+
+        log_event(
+            "ownership_transferred",
+            json!({ "old_owner": self.owner_id, "new_owner": new_owner }),
+        );
+
+        self.owner_id = new_owner;
+    }
+
+    pub fn set_report_threshold(&mut self, threshold: u64) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic_str("NOT_CONTRACT_OWNER");
+        }
+
+        if threshold < 1 {
+            env::panic_str("INVALID_REPORT_THRESHOLD");
+        }
+
+        self.report_threshold = threshold;
+    }
+
+    pub fn set_like_notification_window(&mut self, window: u64) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic_str("NOT_CONTRACT_OWNER");
+        }
+
+        self.like_notification_window = window;
+    }
+
+    // Posts hidden from public listings are returned as None
+    pub fn get_posts_by_ids(&self, ids: Vec<u128>) -> Vec<Option<Post>> {
+        ids.iter()
+            .map(|post_id| self.posts.get(post_id).filter(|post| self.is_public(post)))
+            .collect()
+    }
+
+    pub fn search(&self, keyword: String) -> Vec<Post> {
+        self.posts_by_keyword
+            .get(&keyword.trim().to_lowercase())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .filter(|post| self.is_public(post))
+            .collect()
+    }
+
+    pub fn get_engagement_score(&self, post_id: u128) -> u64 {
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        let tipped_near = (post.total_tips / YOCTO_PER_NEAR) as u64;
+
+        post.like_count
+            .saturating_mul(LIKE_WEIGHT)
+            .saturating_add(
+                self.get_comment_count(post_id)
+                    .saturating_mul(COMMENT_WEIGHT),
+            )
+            .saturating_add(post.view_count.saturating_mul(VIEW_WEIGHT))
+            .saturating_add(tipped_near.saturating_mul(TIP_WEIGHT))
+    }
+
+    pub fn get_similar_posts(&self, post_id: u128, limit: u64) -> Vec<Post> {
+        let post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        // Reposts copy their original's tags, so they'd always be the closest match
+        let original_id = match post.reposted_from {
+            Some(reposted_from) if !post.quote => reposted_from,
+            _ => post_id,
+        };
+
+        // Count the tags each candidate shares with the post
+        let mut overlaps = Vec::<(u128, u64)>::new();
+        for tag in post.tags.iter() {
+            for candidate_id in self.posts_by_tag.get(tag).unwrap_or_default() {
+                if candidate_id == post_id {
+                    continue;
+                }
+
+                match overlaps.iter_mut().find(|(id, _)| id == &candidate_id) {
+                    Some((_, overlap)) => *overlap += 1,
+                    None => overlaps.push((candidate_id, 1)),
+                }
+            }
+        }
+
+        // Most shared tags first, ties broken by the most recent id
+        overlaps.sort_by(|a, b| b.1.cmp(&a.1).then(b.0.cmp(&a.0)));
+
+        overlaps
+            .iter()
+            .filter_map(|(candidate_id, _)| self.posts.get(candidate_id))
+            .filter(|candidate| self.is_public(candidate))
+            .filter(|candidate| candidate.id != original_id)
+            .filter(|candidate| candidate.quote || candidate.reposted_from != Some(original_id))
+            .take(limit as usize)
+            .collect()
+    }
+
+    // Hides the signer's posts and profile without deleting any of their data
+    pub fn deactivate_account(&mut self) {
+        self.deactivated.insert(&env::signer_account_id(), &true);
+    }
+
+    pub fn reactivate_account(&mut self) {
+        self.deactivated.remove(&env::signer_account_id());
+    }
+
+    fn is_deactivated(&self, account_id: &AccountId) -> bool {
+        self.deactivated.get(account_id).unwrap_or(false)
+    }
+
+    pub fn get_quotes_of_post(&self, post_id: u128) -> Vec<Post> {
+        self.quotes_by_post
+            .get(&post_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|quote_id| self.posts.get(quote_id))
+            .filter(|quote| self.is_public(quote))
+            .collect()
+    }
+
+    // total_likes iterates over every post, so the cost grows with the number of posts
+    pub fn get_stats(&self) -> ContractStats {
+        ContractStats {
+            total_posts: self.posts.len(),
+            total_tags: self.public_tag_counts().len() as u64,
+            total_likes: self.posts.values().map(|post| post.like_count).sum(),
+        }
+    }
+
+    pub fn transfer_post(&mut self, post_id: u128, new_owner: AccountId) {
+        let mut post = self
+            .posts
+            .get(&post_id)
+            .unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+
+        if env::signer_account_id() != post.owner_id {
+            env::panic_str("NOT_POST_OWNER");
+        }
+
+        if new_owner == post.owner_id {
+            env::panic_str("ALREADY_POST_OWNER");
+        }
+
+        // Move the post between the owners indexes
+        if let Some(mut owner_posts) = self.posts_by_owner.get(&post.owner_id) {
+            owner_posts.retain(|owner_post_id| owner_post_id != &post_id);
+            self.posts_by_owner.insert(&post.owner_id, &owner_posts);
+        }
+
+        let mut new_owner_posts = self.posts_by_owner.get(&new_owner).unwrap_or_default();
+        new_owner_posts.push(post_id);
+        self.posts_by_owner.insert(&new_owner, &new_owner_posts);
+
+        // The previous owner can't keep someone else's post pinned
+        if self.pinned_post.get(&post.owner_id) == Some(post_id) {
+            self.pinned_post.remove(&post.owner_id);
+        }
+
+        post.owner_id = new_owner;
+        self.posts.insert(&post_id, &post);
+    }
+
+    // Missing posts and ones the signer already likes are skipped
+    pub fn like_posts(&mut self, post_ids: Vec<u128>) -> Vec<Post> {
+        let sender_id = env::signer_account_id();
+        let mut liked_posts = Vec::<Post>::new();
+
+        for post_id in post_ids {
+            if self.has_liked(post_id, sender_id.clone()) {
+                continue;
+            }
+
+            if let Some(post) = self.like_a_post(post_id) {
+                liked_posts.push(post);
+            }
+        }
+
+        liked_posts
+    }
+
+    pub fn get_posts_with_media(&self, from_index: u64, limit: u64) -> Vec<Post> {
+        self.posts
+            .values()
+            .filter(|post| self.is_public(post))
+            .filter(|post| !post.media.is_empty())
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn get_total_likes_received(&self, account_id: AccountId) -> u64 {
+        self.posts_by_owner
+            .get(&account_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|post_id| self.posts.get(post_id))
+            .map(|post| post.users_who_liked.len() as u64)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    // Enough to cover the storage used by any single call in these tests
+    const STORAGE_DEPOSIT: u128 = ONE_NEAR / 10;
+
+    fn setup_contract() -> SocialNetworking {
+        set_signer(accounts(1));
+        SocialNetworking::new(accounts(0))
+    }
+
+    fn set_signer(account_id: AccountId) {
+        set_signer_at(account_id, 0);
+    }
+
+    fn set_signer_with_deposit(account_id: AccountId, attached_deposit: u128) {
+        testing_env!(VMContextBuilder::new()
+            .signer_account_id(account_id.clone())
+            .predecessor_account_id(account_id)
+            .attached_deposit(attached_deposit)
+            .build());
+    }
+
+    fn set_signer_at(account_id: AccountId, block_timestamp: u64) {
+        testing_env!(VMContextBuilder::new()
+            .signer_account_id(account_id.clone())
+            .predecessor_account_id(account_id)
+            .block_timestamp(block_timestamp)
+            .attached_deposit(STORAGE_DEPOSIT)
+            .build());
+    }
+
+    #[test]
+    fn init_contract() {
+        let contract = SocialNetworking::new(accounts(0));
+
+        assert_eq!(contract.owner_id, accounts(0));
+        assert_eq!(contract.number_of_posts, 0);
+        assert!(contract.posts.is_empty());
+        assert!(contract.likes_by_user_id.is_empty());
+        assert!(contract.posts_by_tag.is_empty());
+        assert!(contract.posts_by_owner.is_empty());
+    }
+
+    #[test]
+    fn storage_keys_do_not_collide() {
+        let mut posts: UnorderedMap<u128, u128> = UnorderedMap::new(StorageKey::Posts);
+        let reports: UnorderedMap<u128, u128> = UnorderedMap::new(StorageKey::Reports);
+
+        posts.insert(&0, &1);
+
+        assert_eq!(posts.get(&0), Some(1));
+        assert!(reports.get(&0).is_none());
+        assert!(reports.is_empty());
+    }
+
+    #[test]
+    fn add_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        let new_post = contract.posts.get(&0).expect("Issue getting post in test");
+
+        assert_eq!(contract.number_of_posts, 1);
+        assert_eq!(new_post.title, "Test".to_string());
+        assert_eq!(new_post.tags, vec!["tag1", "tag2", "tag3"]);
+
+        assert_eq!(
+            contract
+                .posts_by_tag
+                .get(&"tag1".to_string())
+                .expect("Error finding posts by tag in test"),
+            vec![0]
+        );
+        assert_eq!(
+            contract
+                .get_posts_by_tag("tag1".to_string(), 0, 10)
+                .get(0)
+                .expect("Error getting first post in test")
+                .title,
+            "Test".to_string()
+        )
+    }
+
+    #[test]
+    fn add_post_media_type() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "clip.mp4".to_string(),
+            "Video".to_string(),
+        );
+
+        assert_eq!(contract.posts.get(&0).unwrap().media_type, MediaType::Video);
+
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        assert_eq!(contract.repost(0).media_type, MediaType::Video);
+    }
+
+    #[test]
+    #[should_panic(expected = "INVALID_MEDIA_TYPE")]
+    fn add_post_invalid_media_type() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "pic".to_string(),
+        );
+    }
+
+    #[test]
+    fn add_post_with_multiple_media() {
+        let mut contract = setup_contract();
+
+        let post = contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "one.png, two.png,three.mp4".to_string(),
+            "image".to_string(),
+        );
+
+        assert_eq!(post.media.len(), 3);
+        assert_eq!(post.media, vec!["one.png", "two.png", "three.mp4"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "TOO_MANY_MEDIA")]
+    fn add_post_with_too_many_media() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            vec!["pic.png"; MAX_MEDIA_PER_POST + 1].join(","),
+            "image".to_string(),
+        );
+    }
+
+    #[test]
+    fn add_post_pays_for_storage() {
+        let mut contract = setup_contract();
+
+        let initial_storage_usage = env::storage_usage();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert!(env::storage_usage() > initial_storage_usage);
+        assert!(contract.get_post_by_id(0, accounts(1)).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "INSUFFICIENT_STORAGE_DEPOSIT")]
+    fn add_post_without_deposit() {
+        let mut contract = setup_contract();
+
+        set_signer_with_deposit(accounts(1), 0);
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+    }
+
+    #[test]
+    fn add_posts() {
+        let mut contract = setup_contract();
+
+        let posts = contract.add_posts(vec![
+            AddPostArgs {
+                title: "Test".to_string(),
+                description: "Test Descritpion".to_string(),
+                tags: "tag1,tag2,tag3".to_string(),
+                media: "post".to_string(),
+                media_type: "text".to_string(),
+            },
+            AddPostArgs {
+                title: "Test2".to_string(),
+                description: "Test Descritpion2".to_string(),
+                tags: "tag4,tag5,tag6".to_string(),
+                media: "video".to_string(),
+                media_type: "video".to_string(),
+            },
+            AddPostArgs {
+                title: "Test3".to_string(),
+                description: "Test Descritpion3".to_string(),
+                tags: "tag1,tag5,tag7".to_string(),
+                media: "pic".to_string(),
+                media_type: "image".to_string(),
+            },
+        ]);
+
+        let ids: Vec<u128> = posts.iter().map(|post| post.id).collect();
+
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert_eq!(contract.get_posts_count(), 3);
+        assert_eq!(
+            contract.get_posts_by_tag("tag5".to_string(), 0, 10).len(),
+            2
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "TITLE_REQUIRED")]
+    fn add_posts_with_invalid_post() {
+        let mut contract = setup_contract();
+
+        contract.add_posts(vec![
+            AddPostArgs {
+                title: "Test".to_string(),
+                description: "Test Descritpion".to_string(),
+                tags: "tag1,tag2,tag3".to_string(),
+                media: "post".to_string(),
+                media_type: "text".to_string(),
+            },
+            AddPostArgs {
+                title: "".to_string(),
+                description: "Test Descritpion2".to_string(),
+                tags: "tag4,tag5,tag6".to_string(),
+                media: "video".to_string(),
+                media_type: "video".to_string(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn add_post_normalizes_tags() {
+        let mut contract = setup_contract();
+
+        let post = contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            " Tag1 , tag1,,TAG2 ".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert_eq!(post.tags, vec!["tag1", "tag2"]);
+        assert_eq!(contract.posts_by_tag.len(), 2);
+        assert_eq!(
+            contract.get_posts_by_tag("Tag1".to_string(), 0, 10).len(),
+            1
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "TITLE_REQUIRED")]
+    fn add_post_empty_title() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "  ".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DESCRIPTION_REQUIRED")]
+    fn add_post_empty_description() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "TITLE_TOO_LONG")]
+    fn add_post_title_too_long() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "a".repeat(MAX_TITLE_LENGTH + 1),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DESCRIPTION_TOO_LONG")]
+    fn add_post_description_too_long() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "a".repeat(MAX_DESCRIPTION_LENGTH + 1),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+    }
+
+    #[test]
+    fn add_post_extracts_hashtags() {
+        let mut contract = setup_contract();
+
+        let post = contract.add_post(
+            "Test".to_string(),
+            "Learning #rust on #NEAR, #rust!".to_string(),
+            "rust,tag1".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert_eq!(post.tags, vec!["rust", "tag1", "near"]);
+        assert_eq!(
+            contract.get_posts_by_tag("rust".to_string(), 0, 10).len(),
+            1
+        );
+        assert_eq!(
+            contract.get_posts_by_tag("near".to_string(), 0, 10).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn add_post_created_at() {
+        let mut contract = setup_contract();
+
+        set_signer_at(accounts(1), 1_000);
+
+        let post = contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert_ne!(post.created_at, 0);
+        assert_eq!(
+            contract
+                .posts
+                .get(&0)
+                .expect("Issue getting post in test")
+                .created_at,
+            1_000
+        );
+    }
+
+    #[test]
+    fn repost() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        let repost = contract.repost(0);
+
+        assert_eq!(repost.id, 1);
+        assert_eq!(repost.reposted_from, Some(0));
+        assert_eq!(repost.owner_id, accounts(2));
+        assert_eq!(repost.title, "Test".to_string());
+        assert_eq!(repost.tags, vec!["tag1", "tag2", "tag3"]);
+        assert_eq!(contract.posts.get(&0).unwrap().owner_id, accounts(1));
+        assert_eq!(
+            contract.get_posts_by_tag("tag1".to_string(), 0, 10).len(),
+            2
+        );
+        assert!(contract.posts.get(&0).unwrap().reposted_from.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn repost_missing_post() {
+        let mut contract = setup_contract();
+
+        contract.repost(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn repost_draft() {
+        let mut contract = setup_contract();
+
+        contract.add_draft(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.repost(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn quote_deleted_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.soft_delete_post(0);
+
+        set_signer(accounts(2));
+        contract.quote_post(0, "Look at this".to_string());
+    }
+
+    #[test]
+    fn get_all_posts() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let all_posts = contract.get_all_posts(0, 10);
+
+        assert_eq!(all_posts.len(), 3);
+        assert_eq!(
+            all_posts
+                .get(2)
+                .expect("Trouble getting all posts in test")
+                .1
+                .title,
+            "Test3"
+        );
+    }
+
+    #[test]
+    fn get_all_posts_paginated() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let page = contract.get_all_posts(1, 1);
+
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().0, 1);
+        assert_eq!(page.get(0).unwrap().1.title, "Test2".to_string());
+        assert!(contract.get_all_posts(5, 10).is_empty());
+    }
+
+    #[test]
+    fn get_posts_count() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        assert_eq!(contract.get_posts_count(), 3);
+    }
+
+    #[test]
+    fn get_post_by_id() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        assert_eq!(
+            contract
+                .get_post_by_id(1, accounts(1))
+                .expect("Error getting post by id in test")
+                .title,
+            "Test2".to_string()
+        );
+        assert!(contract.get_post_by_id(7, accounts(1)).is_none());
+    }
+
+    #[test]
+    fn get_posts_by_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let posts = contract.get_posts_by_owner(accounts(1), 0, 10);
+
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts.get(0).unwrap().id, 1);
+        assert_eq!(posts.get(1).unwrap().id, 0);
+        assert_eq!(contract.get_posts_by_owner(accounts(2), 0, 10).len(), 1);
+        assert!(contract.get_posts_by_owner(accounts(3), 0, 10).is_empty());
+    }
+
+    #[test]
+    fn edit_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.like_a_post(0);
+        contract.edit_post(
+            0,
+            "Edited".to_string(),
+            "Test Description".to_string(),
+            "video".to_string(),
+        );
+
+        let all_posts = contract.get_all_posts(0, 10);
+        let edited_post = &all_posts.get(0).unwrap().1;
+
+        assert_eq!(edited_post.title, "Edited".to_string());
+        assert_eq!(edited_post.tags, vec!["tag1", "tag2", "tag3"]);
+        assert_eq!(edited_post.users_who_liked.len(), 1);
+        assert_eq!(
+            contract
+                .get_posts_by_tag("tag2".to_string(), 0, 10)
+                .get(0)
+                .unwrap()
+                .title,
+            "Edited".to_string()
+        );
+        assert_eq!(
+            contract.get_liked_posts().get(0).unwrap().title,
+            "Edited".to_string()
+        );
+    }
+
+    #[test]
+    fn get_edit_history() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_at(accounts(1), 10);
+        contract.edit_post(
+            0,
+            "Edited".to_string(),
+            "Test Description".to_string(),
+            "post".to_string(),
+        );
+
+        set_signer_at(accounts(1), 20);
+        contract.edit_post(
+            0,
+            "Edited again".to_string(),
+            "Test Description".to_string(),
+            "post".to_string(),
+        );
+
+        assert_eq!(
+            contract.get_edit_history(0),
+            vec![(10, "Test".to_string()), (20, "Edited".to_string())]
+        );
+        assert!(contract.get_edit_history(1).is_empty());
+    }
+
+    #[test]
+    fn edit_history_is_bounded() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        for edit in 0..MAX_EDIT_HISTORY + 1 {
+            contract.edit_post(
+                0,
+                format!("Edit {}", edit),
+                "Test Description".to_string(),
+                "post".to_string(),
+            );
+        }
+
+        let edit_history = contract.get_edit_history(0);
+
+        assert_eq!(edit_history.len(), MAX_EDIT_HISTORY);
+        assert_eq!(edit_history.get(0).unwrap().1, "Edit 0".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_POST_OWNER")]
+    fn edit_post_not_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.edit_post(
+            0,
+            "Edited".to_string(),
+            "Test Description".to_string(),
+            "video".to_string(),
+        );
+    }
+
+    #[test]
+    fn soft_delete_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        contract.soft_delete_post(0);
+
+        set_signer(accounts(2));
+        contract.follow(accounts(1));
+
+        let all_posts = contract.get_all_posts(0, 10);
+
+        assert_eq!(all_posts.len(), 1);
+        assert_eq!(all_posts.get(0).unwrap().0, 1);
+        assert_eq!(
+            contract.get_posts_by_tag("tag1".to_string(), 0, 10).len(),
+            1
+        );
+        assert!(contract
+            .get_posts_by_tag("tag2".to_string(), 0, 10)
+            .is_empty());
+        assert_eq!(contract.get_feed(accounts(2), 0, 10).len(), 1);
+        assert!(
+            contract
+                .get_post_by_id(0, accounts(1))
+                .expect("Post not found")
+                .deleted
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_POST_OWNER")]
+    fn soft_delete_post_not_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.soft_delete_post(0);
+    }
+
+    #[test]
+    fn delete_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        contract.like_a_post(0);
+        contract.delete_post(0);
+
+        let all_posts = contract.get_all_posts(0, 10);
+        let posts = contract.get_posts_by_tag("tag1".to_string(), 0, 10);
+
+        assert_eq!(all_posts.len(), 1);
+        assert_eq!(all_posts.get(0).unwrap().1.title, "Test2".to_string());
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts.get(0).unwrap().title, "Test2".to_string());
+        assert!(contract.posts_by_tag.get(&"tag2".to_string()).is_none());
+        assert!(contract.get_liked_posts().is_empty());
+    }
+
+    #[test]
+    fn delete_post_frees_storage() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        let storage_usage = env::storage_usage();
+
+        contract.delete_post(0);
+
+        assert!(env::storage_usage() < storage_usage);
+    }
+
+    #[test]
+    fn storage_payer_is_predecessor() {
+        let mut contract = setup_contract();
+
+        // A contract posting on behalf of its user attaches the deposit itself
+        testing_env!(VMContextBuilder::new()
+            .signer_account_id(accounts(1))
+            .predecessor_account_id(accounts(5))
+            .attached_deposit(STORAGE_DEPOSIT)
+            .build());
+        let post = contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert_eq!(post.owner_id, accounts(1));
+        assert_eq!(post.storage_payer, accounts(5));
+    }
+
+    #[test]
+    fn delete_post_refund_capped_at_storage_paid() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        let storage_paid = contract.posts.get(&0).unwrap().storage_paid;
+        assert!(storage_paid > 0);
+
+        // Comments grow the post's storage without being paid for by its creator
+        set_signer(accounts(2));
+        contract.add_comment(0, "a".repeat(MAX_COMMENT_LENGTH), None);
+
+        set_signer(accounts(1));
+        contract.transfer_post(0, accounts(3));
+
+        set_signer(accounts(3));
+        contract.delete_post(0);
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(1));
+        match &receipts[0].actions[..] {
+            [VmAction::Transfer { deposit }] => {
+                assert_eq!(*deposit, storage_paid as u128 * env::storage_byte_cost())
+            }
+            _ => panic!("expected a single transfer"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_POST_OWNER")]
+    fn delete_post_not_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.delete_post(0);
+    }
+
+    #[test]
+    fn like_a_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        let returned_post = contract.like_a_post(0).expect("Post not found");
+
+        let liked_post = contract.posts.get(&0).expect("Post not liked");
+
+        assert_eq!(returned_post.users_who_liked, liked_post.users_who_liked);
+        assert_eq!(liked_post.users_who_liked.len(), 1);
+        assert_eq!(liked_post.like_count, 1);
+        assert_eq!(
+            liked_post.like_count,
+            liked_post.users_who_liked.len() as u64
+        );
+        assert_eq!(
+            liked_post
+                .users_who_liked
+                .get(0)
+                .expect("Error finding uses who liked the post"),
+            &env::signer_account_id()
+        );
+    }
+
+    #[test]
+    fn like_a_post_updates_posts_by_tag() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.like_a_post(0);
+
+        let posts = contract.get_posts_by_tag("tag2".to_string(), 0, 10);
+        let tagged_post = posts.get(0).expect("Error getting tagged post in test");
+
+        assert_eq!(tagged_post.users_who_liked, vec![env::signer_account_id()]);
+        assert_eq!(tagged_post.like_count, 1);
+
+        contract.unlike_a_post(0);
+
+        assert!(contract
+            .get_posts_by_tag("tag2".to_string(), 0, 10)
+            .get(0)
+            .unwrap()
+            .users_who_liked
+            .is_empty());
+    }
+
+    #[test]
+    fn like_a_missing_post() {
+        let mut contract = setup_contract();
+
+        assert!(contract.like_a_post(999).is_none());
+        assert!(contract
+            .likes_by_user_id
+            .get(&env::signer_account_id())
+            .is_none());
+    }
+
+    #[test]
+    fn like_a_post_twice() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.like_a_post(0);
+        contract.like_a_post(0);
+
+        let liked_post = contract.posts.get(&0).expect("Post not liked");
+
+        assert_eq!(liked_post.users_who_liked.len(), 1);
+        assert_eq!(liked_post.like_count, 1);
+        assert_eq!(contract.get_liked_posts().len(), 1);
+    }
+
+    #[test]
+    fn add_and_like_post_events() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.like_a_post(0);
+
+        let events: Vec<serde_json::Value> = get_logs()
+            .iter()
+            .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+            .map(|event| serde_json::from_str(event).expect("Invalid event JSON in test"))
+            .collect();
+
+        assert_eq!(
+            events,
+            vec![
+                json!({
+                    "standard": EVENT_STANDARD,
+                    "version": EVENT_VERSION,
+                    "event": "post_created",
+                    "data": [{ "post_id": 0, "owner_id": accounts(1) }],
+                }),
+                json!({
+                    "standard": EVENT_STANDARD,
+                    "version": EVENT_VERSION,
+                    "event": "post_liked",
+                    "data": [{ "post_id": 0, "liker": accounts(1) }],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn unlike_a_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.like_a_post(0);
+        contract.unlike_a_post(0);
+
+        let unliked_post = contract.posts.get(&0).expect("Post not found");
+
+        assert!(unliked_post.users_who_liked.is_empty());
+        assert_eq!(unliked_post.like_count, 0);
+        assert!(contract.get_liked_posts().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_LIKED")]
+    fn unlike_a_post_not_liked() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.unlike_a_post(0);
+    }
+
+    #[test]
+    fn toggle_like() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert!(contract.toggle_like(0));
+        assert_eq!(contract.posts.get(&0).unwrap().like_count, 1);
+
+        assert!(!contract.toggle_like(0));
+        assert_eq!(contract.posts.get(&0).unwrap().like_count, 0);
+    }
+
+    #[test]
+    fn get_post_likers() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.like_a_post(0);
+
+        set_signer(accounts(3));
+        contract.like_a_post(0);
+
+        assert_eq!(
+            contract.get_post_likers(0, 0, 10),
+            vec![accounts(2), accounts(3)]
+        );
+        assert_eq!(contract.get_post_likers(0, 1, 1), vec![accounts(3)]);
+        assert!(contract.get_post_likers(0, 2, 10).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn get_post_likers_missing_post() {
+        let contract = setup_contract();
+
+        contract.get_post_likers(0, 0, 10);
+    }
+
+    #[test]
+    fn has_liked() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.like_a_post(0);
+
+        assert!(contract.has_liked(0, accounts(2)));
+        assert!(!contract.has_liked(0, accounts(3)));
+        assert!(!contract.has_liked(99, accounts(2)));
+    }
+
+    #[test]
+    fn get_liked_posts() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        contract.like_a_post(0);
+        contract.like_a_post(1);
+
+        assert_eq!(
+            contract
+                .get_liked_posts()
+                .get(0)
+                .unwrap_or_else(|| env::panic_str("ERROR FINDING LIKED POSTS"))
+                .title,
+            "Test".to_string()
+        );
+        assert_eq!(contract.get_liked_posts().len(), 2);
+    }
+
+    #[test]
+    fn get_liked_posts_without_likes() {
+        let contract = setup_contract();
+
+        assert!(contract.get_liked_posts().is_empty());
+    }
+
+    #[test]
+    fn get_liked_posts_after_edit() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.like_a_post(0);
+        contract.edit_post(
+            0,
+            "Edited".to_string(),
+            "Test Description".to_string(),
+            "video".to_string(),
+        );
+
+        assert_eq!(
+            contract.likes_by_user_id.get(&env::signer_account_id()),
+            Some(vec![0])
+        );
+        assert_eq!(
+            contract.get_liked_posts().get(0).unwrap().title,
+            "Edited".to_string()
+        );
+    }
+
+    #[test]
+    fn get_liked_posts_for_account() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.like_a_post(0);
+
+        let liked_posts = contract.get_liked_posts_for_account(accounts(2));
+
+        assert_eq!(liked_posts.len(), 1);
+        assert_eq!(liked_posts.get(0).unwrap().title, "Test".to_string());
+        assert!(contract.get_liked_posts_for_account(accounts(3)).is_empty());
+    }
+
+    #[test]
+    fn get_posts_by_tag_after_like() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag1,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        contract.like_a_post(1);
+
+        let posts = contract.get_posts_by_tag("tag1".to_string(), 0, 10);
+
+        assert_eq!(
+            contract.posts_by_tag.get(&"tag1".to_string()),
+            Some(vec![0, 1])
+        );
+        assert_eq!(posts.get(0).unwrap().like_count, 0);
+        assert_eq!(posts.get(1).unwrap().like_count, 1);
+    }
+
+    #[test]
+    fn get_posts_by_tag() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let posts = contract.get_posts_by_tag("tag5".to_string(), 0, 10);
+
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts.get(0).unwrap().title, "Test2".to_string());
+        assert_eq!(posts.get(1).unwrap().title, "Test3".to_string());
+    }
+
+    #[test]
+    fn add_comment() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.add_comment(0, "First".to_string(), None);
+        contract.add_comment(0, "Second".to_string(), None);
+
+        let comments = contract.get_comments(0);
+
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments.get(0).unwrap().text, "First".to_string());
+        assert_eq!(comments.get(1).unwrap().text, "Second".to_string());
+        assert_eq!(comments.get(1).unwrap().id, 1);
+        assert_eq!(comments.get(1).unwrap().author, env::signer_account_id());
+        assert!(contract.get_comments(1).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "COMMENT_TOO_LONG")]
+    fn add_comment_too_long() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.add_comment(0, "a".repeat(MAX_COMMENT_LENGTH + 1), None);
+    }
+
+    #[test]
+    fn delete_post_removes_comments() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.add_comment(0, "First".to_string(), None);
+
+        contract.delete_post(0);
+
+        assert!(contract.get_comments(0).is_empty());
+        assert_eq!(contract.get_comment_count(0), 0);
+        assert!(contract.comments_by_post.get(&0).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn add_comment_missing_post() {
+        let mut contract = setup_contract();
+
+        contract.add_comment(0, "First".to_string(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "COMMENTS_DISABLED")]
+    fn add_comment_disabled() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.set_comments_enabled(0, false);
+
+        set_signer(accounts(2));
+        contract.add_comment(0, "First".to_string(), None);
+    }
+
+    #[test]
+    fn set_comments_enabled() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert_eq!(
+            contract.posts.get(&0).unwrap().comment_policy,
+            CommentPolicy::Anyone
+        );
+
+        contract.set_comments_enabled(0, false);
+
+        assert_eq!(
+            contract.posts.get(&0).unwrap().comment_policy,
+            CommentPolicy::Nobody
+        );
+
+        contract.set_comments_enabled(0, true);
+        contract.add_comment(0, "First".to_string(), None);
+
+        assert_eq!(contract.get_comments(0).len(), 1);
+    }
+
+    #[test]
+    fn like_comment() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.add_comment(0, "First".to_string(), None);
+
+        set_signer(accounts(2));
+        contract.like_comment(0, 0);
+        contract.like_comment(0, 0);
+
+        let comments = contract.get_comments(0);
+
+        assert_eq!(comments.get(0).unwrap().liked_by, vec![accounts(2)]);
+        assert_eq!(contract.get_comment_like_count(0, 0), 1);
+        assert_eq!(contract.get_comment_like_count(0, 5), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "COMMENT_NOT_FOUND")]
+    fn like_missing_comment() {
+        let mut contract = setup_contract();
+
+        contract.like_comment(0, 0);
+    }
+
+    #[test]
+    fn delete_comment() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.add_comment(0, "First".to_string(), None);
+
+        set_signer(accounts(3));
+        contract.add_comment(0, "Second".to_string(), None);
+        contract.delete_comment(0, 1);
+
+        let comments = contract.get_comments(0);
+
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments.get(0).unwrap().text, "First".to_string());
+
+        set_signer(accounts(2));
+        contract.delete_comment(0, 0);
+
+        assert!(contract.get_comments(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_COMMENT_AUTHOR")]
+    fn delete_comment_not_author() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.add_comment(0, "First".to_string(), None);
+
+        set_signer(accounts(3));
+        contract.delete_comment(0, 0);
+    }
+
+    #[test]
+    fn get_replies() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        let comment = contract.add_comment(0, "First".to_string(), None);
+        contract.add_comment(0, "Reply".to_string(), Some(comment.id));
+        contract.add_comment(0, "Second".to_string(), None);
+        contract.add_comment(0, "Another reply".to_string(), Some(comment.id));
+
+        let replies = contract.get_replies(0, comment.id);
+
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies.get(0).unwrap().text, "Reply".to_string());
+        assert_eq!(replies.get(1).unwrap().text, "Another reply".to_string());
+        assert!(contract.get_replies(0, 2).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "PARENT_COMMENT_NOT_FOUND")]
+    fn add_reply_to_comment_on_another_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        let comment = contract.add_comment(0, "First".to_string(), None);
+        contract.add_comment(1, "Reply".to_string(), Some(comment.id));
+    }
+
+    #[test]
+    fn follow() {
+        let mut contract = setup_contract();
+
+        contract.follow(accounts(2));
+        contract.follow(accounts(3));
+
+        assert_eq!(
+            contract.get_following(accounts(1)),
+            vec![accounts(2), accounts(3)]
+        );
+    }
+
+    #[test]
+    fn follow_twice() {
+        let mut contract = setup_contract();
+
+        contract.follow(accounts(2));
+        contract.follow(accounts(2));
+
+        assert_eq!(contract.get_following(accounts(1)), vec![accounts(2)]);
+        assert_eq!(contract.get_follower_count(accounts(2)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "CANNOT_FOLLOW_SELF")]
+    fn follow_self() {
+        let mut contract = setup_contract();
+
+        contract.follow(accounts(1));
+    }
+
+    #[test]
+    fn unfollow() {
+        let mut contract = setup_contract();
+
+        contract.follow(accounts(2));
+        contract.follow(accounts(3));
+        contract.unfollow(accounts(2));
+        contract.unfollow(accounts(4));
+
+        assert_eq!(contract.get_following(accounts(1)), vec![accounts(3)]);
+        assert!(contract.get_following(accounts(2)).is_empty());
+        assert!(contract.get_followers(accounts(2), 0, 10).is_empty());
+        assert_eq!(
+            contract.get_followers(accounts(3), 0, 10),
+            vec![accounts(1)]
+        );
+    }
+
+    #[test]
+    fn get_mutual_follows() {
+        let mut contract = setup_contract();
+
+        set_signer(accounts(0));
+        contract.follow(accounts(3));
+        contract.follow(accounts(4));
+        contract.follow(accounts(2));
+
+        set_signer(accounts(1));
+        contract.follow(accounts(2));
+        contract.follow(accounts(3));
+
+        assert_eq!(
+            contract.get_mutual_follows(accounts(0), accounts(1)),
+            vec![accounts(2), accounts(3)]
+        );
+        assert!(contract
+            .get_mutual_follows(accounts(0), accounts(5))
+            .is_empty());
+    }
+
+    #[test]
+    fn get_followers() {
+        let mut contract = setup_contract();
+
+        set_signer(accounts(0));
+        contract.follow(accounts(1));
+
+        set_signer(accounts(2));
+        contract.follow(accounts(1));
+
+        assert_eq!(
+            contract.get_followers(accounts(1), 0, 10),
+            vec![accounts(0), accounts(2)]
+        );
+        assert_eq!(contract.get_follower_count(accounts(1)), 2);
+        assert_eq!(contract.get_follower_count(accounts(0)), 0);
+    }
+
+    #[test]
+    fn get_feed() {
+        let mut contract = setup_contract();
+
+        set_signer_at(accounts(2), 1);
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_at(accounts(3), 2);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        set_signer_at(accounts(4), 3);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        set_signer_at(accounts(2), 1 + DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test4".to_string(),
+            "Test Descritpion4".to_string(),
+            "tag1".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        set_signer(accounts(0));
+        contract.follow(accounts(2));
+        contract.follow(accounts(3));
+
+        let feed = contract.get_feed(accounts(0), 0, 10);
+        let titles: Vec<String> = feed.iter().map(|post| post.title.clone()).collect();
+
+        assert_eq!(titles, vec!["Test4", "Test2", "Test"]);
+        assert_eq!(
+            contract.get_feed(accounts(0), 1, 1).get(0).unwrap().title,
+            "Test2"
+        );
+        assert!(contract.get_feed(accounts(5), 0, 10).is_empty());
+    }
+
+    #[test]
+    fn search_posts_by_title() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Rust news".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Cooking".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        let posts = contract.search_posts_by_title("rust".to_string(), 0, 10);
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts.get(0).unwrap().title, "Rust news".to_string());
+        assert!(contract
+            .search_posts_by_title("rust".to_string(), 1, 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn get_posts_by_unknown_tag() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert!(contract
+            .get_posts_by_tag("tag9".to_string(), 0, 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn bookmark_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        contract.bookmark_post(1);
+        contract.bookmark_post(0);
+
+        let bookmarks = contract.get_bookmarks();
+
+        assert_eq!(bookmarks.len(), 2);
+        assert_eq!(bookmarks.get(0).unwrap().title, "Test2".to_string());
+        assert_eq!(bookmarks.get(1).unwrap().title, "Test".to_string());
+        assert!(contract.get_liked_posts().is_empty());
+    }
+
+    #[test]
+    fn bookmark_post_twice() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.bookmark_post(0);
+        contract.bookmark_post(0);
+
+        assert_eq!(contract.get_bookmarks().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn bookmark_missing_post() {
+        let mut contract = setup_contract();
+
+        contract.bookmark_post(0);
+    }
+
+    #[test]
+    fn remove_bookmark() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.bookmark_post(0);
+        contract.remove_bookmark(0);
+
+        assert!(contract.get_bookmarks().is_empty());
+    }
+
+    #[test]
+    fn increment_view_count() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.increment_view_count(0);
+        assert_eq!(contract.posts.get(&0).unwrap().view_count, 0);
+
+        set_signer(accounts(2));
+        contract.increment_view_count(0);
+        contract.increment_view_count(0);
+        assert_eq!(contract.posts.get(&0).unwrap().view_count, 2);
+    }
+
+    #[test]
+    fn get_trending_posts() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        contract.like_a_post(1);
+        contract.like_a_post(2);
+
+        set_signer(accounts(2));
+        contract.like_a_post(1);
+
+        let ids: Vec<u128> = contract
+            .get_trending_posts(10)
+            .iter()
+            .map(|post| post.id)
+            .collect();
+
+        assert_eq!(ids, vec![1, 2, 0]);
+        assert_eq!(contract.get_trending_posts(1).len(), 1);
+    }
+
+    #[test]
+    fn block_user() {
+        let mut contract = setup_contract();
+
+        contract.block_user(accounts(2));
+        contract.block_user(accounts(2));
+        contract.block_user(accounts(3));
+
+        assert_eq!(contract.get_blocked(), vec![accounts(2), accounts(3)]);
+        assert!(contract.is_blocked(accounts(1), accounts(2)));
+        assert!(!contract.is_blocked(accounts(2), accounts(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "CANNOT_BLOCK_SELF")]
+    fn block_self() {
+        let mut contract = setup_contract();
+
+        contract.block_user(accounts(1));
+    }
+
+    #[test]
+    fn unblock_user() {
+        let mut contract = setup_contract();
+
+        contract.block_user(accounts(2));
+        contract.block_user(accounts(3));
+        contract.unblock_user(accounts(2));
+
+        assert_eq!(contract.get_blocked(), vec![accounts(3)]);
+        assert!(!contract.is_blocked(accounts(1), accounts(2)));
+        assert!(contract.is_blocked(accounts(1), accounts(3)));
+    }
+
+    #[test]
+    fn tip_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_with_deposit(accounts(2), ONE_NEAR);
+        contract.tip_post(0);
+        contract.tip_post(0);
+
+        assert_eq!(contract.posts.get(&0).unwrap().total_tips, 2 * ONE_NEAR);
+    }
+
+    #[test]
+    #[should_panic(expected = "TIP_REQUIRED")]
+    fn tip_post_without_deposit() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_with_deposit(accounts(2), 0);
+        contract.tip_post(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "CANNOT_TIP_OWN_POST")]
+    fn tip_own_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_with_deposit(accounts(1), ONE_NEAR);
+        contract.tip_post(0);
+    }
+
+    #[test]
+    fn get_all_tags() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag2".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let mut tags = contract.get_all_tags();
+        tags.sort();
+
+        assert_eq!(tags, vec!["tag1", "tag2", "tag3", "tag5"]);
+    }
+
+    #[test]
+    fn get_tag_counts() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag2,tag1".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag1".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        assert_eq!(
+            contract.get_tag_counts(),
+            vec![("tag1".to_string(), 2), ("tag2".to_string(), 1)]
+        );
+
+        // Counts match get_post_count_by_tag, and tags left without public posts drop out
+        contract.soft_delete_post(0);
+
+        assert_eq!(contract.get_tag_counts(), vec![("tag1".to_string(), 1)]);
+        assert_eq!(contract.get_post_count_by_tag("tag1".to_string()), 1);
+        assert_eq!(contract.get_all_tags(), vec!["tag1"]);
+        assert_eq!(contract.get_stats().total_tags, 1);
+    }
+
+    #[test]
+    fn update_post_tags() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag2".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        contract.update_post_tags(0, "tag9, tag2".to_string());
+
+        assert_eq!(contract.posts.get(&0).unwrap().tags, vec!["tag9", "tag2"]);
+        assert!(contract
+            .get_posts_by_tag("tag1".to_string(), 0, 10)
+            .is_empty());
+        assert!(contract.posts_by_tag.get(&"tag1".to_string()).is_none());
+        assert_eq!(
+            contract
+                .get_posts_by_tag("tag9".to_string(), 0, 10)
+                .get(0)
+                .unwrap()
+                .id,
+            0
+        );
+        assert_eq!(
+            contract.posts_by_tag.get(&"tag2".to_string()),
+            Some(vec![1, 0])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_POST_OWNER")]
+    fn update_post_tags_not_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.update_post_tags(0, "tag9".to_string());
+    }
+
+    #[test]
+    fn set_profile() {
+        let mut contract = setup_contract();
+
+        contract.set_profile(
+            "Bob".to_string(),
+            "Building on NEAR".to_string(),
+            "bob.png".to_string(),
+        );
+
+        let profile = contract
+            .get_profile(accounts(1))
+            .expect("Error getting profile in test");
+
+        assert_eq!(profile.display_name, "Bob".to_string());
+        assert_eq!(profile.bio, "Building on NEAR".to_string());
+        assert_eq!(profile.avatar_url, "bob.png".to_string());
+    }
+
+    #[test]
+    fn get_missing_profile() {
+        let contract = setup_contract();
+
+        assert!(contract.get_profile(accounts(2)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "BIO_TOO_LONG")]
+    fn set_profile_bio_too_long() {
+        let mut contract = setup_contract();
+
+        contract.set_profile(
+            "Bob".to_string(),
+            "a".repeat(MAX_BIO_LENGTH + 1),
+            "bob.png".to_string(),
+        );
+    }
+
+    #[test]
+    fn pin_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        contract.pin_post(1);
+
+        assert_eq!(
+            contract
+                .get_pinned_post(accounts(1))
+                .expect("Error getting pinned post in test")
+                .title,
+            "Test2".to_string()
+        );
+        assert!(contract.get_pinned_post(accounts(2)).is_none());
+    }
+
+    #[test]
+    fn unpin_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.pin_post(0);
+        contract.unpin_post();
+
+        assert!(contract.get_pinned_post(accounts(1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_POST_OWNER")]
+    fn pin_post_not_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.pin_post(0);
+    }
+
+    #[test]
+    fn report_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.report_post(0, "Spam".to_string());
+
+        set_signer(accounts(3));
+        contract.report_post(0, "Offensive".to_string());
+
+        assert_eq!(
+            contract.get_reports(0),
+            vec![
+                (accounts(2), "Spam".to_string()),
+                (accounts(3), "Offensive".to_string())
+            ]
+        );
+        assert!(contract.get_reports(1).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "ALREADY_REPORTED")]
+    fn report_post_twice() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.report_post(0, "Spam".to_string());
+        contract.report_post(0, "Spam again".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "REASON_TOO_LONG")]
+    fn report_post_reason_too_long() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.report_post(0, "a".repeat(MAX_REPORT_REASON_LENGTH + 1));
+    }
+
+    #[test]
+    fn admin_remove_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.like_a_post(0);
+
+        set_signer(accounts(2));
+        contract.report_post(0, "Spam".to_string());
+
+        set_signer(accounts(0));
+        contract.admin_remove_post(0);
+
+        assert!(contract.get_post_by_id(0, accounts(1)).is_none());
+        assert!(contract
+            .get_posts_by_tag("tag1".to_string(), 0, 10)
+            .is_empty());
+        assert!(contract.get_posts_by_owner(accounts(1), 0, 10).is_empty());
+        assert!(contract.get_liked_posts_for_account(accounts(1)).is_empty());
+        assert!(contract.get_reports(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_CONTRACT_OWNER")]
+    fn admin_remove_post_not_admin() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.admin_remove_post(0);
+    }
+
+    #[test]
+    fn get_recent_posts() {
+        let mut contract = setup_contract();
+
+        set_signer_at(accounts(1), 20);
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_at(accounts(2), 10);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        set_signer_at(accounts(3), 20);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let ids: Vec<u128> = contract
+            .get_recent_posts(accounts(0), 0, 10)
+            .iter()
+            .map(|post| post.id)
+            .collect();
+
+        assert_eq!(ids, vec![2, 0, 1]);
+        assert_eq!(
+            contract
+                .get_recent_posts(accounts(0), 1, 1)
+                .get(0)
+                .unwrap()
+                .id,
+            0
+        );
+    }
+
+    #[test]
+    fn get_posts_by_tags_match_all() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let posts = contract.get_posts_by_tags(vec!["tag1".to_string(), "TAG5".to_string()], true);
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts.get(0).unwrap().title, "Test3".to_string());
+    }
+
+    #[test]
+    fn get_posts_by_tags_match_any() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+        set_signer_at(accounts(1), 2 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test3".to_string(),
+            "Test Descritpion3".to_string(),
+            "tag1,tag5,tag7".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+        set_signer_at(accounts(1), 3 * DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test4".to_string(),
+            "Test Descritpion4".to_string(),
+            "tag9".to_string(),
+            "pic".to_string(),
+            "image".to_string(),
+        );
+
+        let ids: Vec<u128> = contract
+            .get_posts_by_tags(vec!["tag1".to_string(), "tag5".to_string()], false)
+            .iter()
+            .map(|post| post.id)
+            .collect();
+
+        assert_eq!(ids, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn blocked_authors_are_hidden() {
+        let mut contract = setup_contract();
+
+        set_signer(accounts(2));
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(3));
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        set_signer(accounts(0));
+        contract.follow(accounts(2));
+        contract.follow(accounts(3));
+        contract.block_user(accounts(2));
+
+        let feed = contract.get_feed(accounts(0), 0, 10);
+        let recent_posts = contract.get_recent_posts(accounts(0), 0, 10);
+
+        assert_eq!(feed.len(), 1);
+        assert_eq!(feed.get(0).unwrap().owner_id, accounts(3));
+        assert_eq!(recent_posts.len(), 1);
+        assert_eq!(recent_posts.get(0).unwrap().owner_id, accounts(3));
+        assert_eq!(contract.get_recent_posts(accounts(1), 0, 10).len(), 2);
+    }
+
+    #[test]
+    fn get_mentions() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Thanks @bob.near, @charlie.near. and @Not Valid!".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        let mentions = contract.get_mentions("bob.near".parse().unwrap());
+
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions.get(0).unwrap().id, 0);
+        assert_eq!(contract.get_mentions(accounts(2)).len(), 1);
+        assert_eq!(contract.mentions_by_user.len(), 2);
+    }
+
+    #[test]
+    fn like_notifies_post_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.like_a_post(0);
+
+        // Nobody else has been notified
+        assert_eq!(contract.get_notifications(0, 10).len(), 0);
+
+        set_signer(accounts(1));
+        let notifications = contract.get_notifications(0, 10);
+
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::Like);
+        assert_eq!(notifications[0].actor, accounts(2));
+        assert_eq!(notifications[0].post_id, Some(0));
+
+        contract.clear_notifications();
+
+        assert_eq!(contract.get_notifications(0, 10).len(), 0);
+    }
+
+    #[test]
+    fn mark_notifications_read() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.like_a_post(0);
+        contract.follow(accounts(1));
+
+        set_signer(accounts(1));
+        assert_eq!(contract.get_unread_count(), 2);
+
+        contract.mark_notifications_read();
+
+        assert_eq!(contract.get_unread_count(), 0);
+        assert_eq!(contract.get_notifications(0, 10).len(), 2);
+    }
+
+    #[test]
+    fn followers_only_posts() {
+        let mut contract = setup_contract();
+
+        contract.add_post_with_visibility(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+            "followers_only".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.follow(accounts(1));
+
+        // The follower sees the post
+        assert_eq!(contract.get_feed(accounts(2), 0, 10).len(), 1);
+        assert_eq!(contract.get_recent_posts(accounts(2), 0, 10).len(), 1);
+        assert!(contract.get_post_by_id(0, accounts(2)).is_some());
+
+        // A non-follower gets nothing, listings without a viewer only show public posts
+        assert!(contract.get_recent_posts(accounts(3), 0, 10).is_empty());
+        assert!(contract.get_all_posts(0, 10).is_empty());
+        assert!(contract
+            .get_posts_by_tag("tag1".to_string(), 0, 10)
+            .is_empty());
+        assert!(contract.get_posts_by_owner(accounts(1), 0, 10).is_empty());
+        assert!(contract.get_post_by_id(0, accounts(3)).is_none());
+
+        // The author always sees their own post
+        assert!(contract.get_post_by_id(0, accounts(1)).is_some());
+    }
+
+    #[test]
+    fn like_followers_only_post_as_non_follower() {
+        let mut contract = setup_contract();
+
+        contract.add_post_with_visibility(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+            "followers_only".to_string(),
+        );
+
+        set_signer(accounts(2));
+        assert!(contract.like_a_post(0).is_none());
+        assert_eq!(contract.posts.get(&0).unwrap().like_count, 0);
+        assert!(contract.get_liked_posts().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn bookmark_draft_of_another_account() {
+        let mut contract = setup_contract();
+
+        contract.add_draft(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.bookmark_post(0);
+    }
+
+    #[test]
+    fn drafts_are_hidden_until_published() {
+        let mut contract = setup_contract();
+
+        contract.add_draft(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert!(contract.get_all_posts(0, 10).is_empty());
+        assert!(contract.get_posts_by_owner(accounts(1), 0, 10).is_empty());
+        assert!(contract
+            .get_posts_by_tag("tag1".to_string(), 0, 10)
+            .is_empty());
+        assert!(contract.get_recent_posts(accounts(2), 0, 10).is_empty());
+        assert!(contract.get_post_by_id(0, accounts(2)).is_none());
+        assert_eq!(contract.get_my_drafts().len(), 1);
+
+        set_signer_at(accounts(1), 5);
+        let post = contract.publish_post(0);
+
+        assert_eq!(post.created_at, 5);
+        assert_eq!(contract.get_all_posts(0, 10).len(), 1);
+        assert_eq!(
+            contract.get_posts_by_tag("tag1".to_string(), 0, 10).len(),
+            1
+        );
+        assert!(contract.get_my_drafts().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_POST_OWNER")]
+    fn publish_post_not_owner() {
+        let mut contract = setup_contract();
+
+        contract.add_draft(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.publish_post(0);
+    }
+
+    #[test]
+    fn scheduled_posts_are_hidden_until_publish_at() {
+        let mut contract = setup_contract();
+
+        contract.add_draft(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.schedule_post(0, 100);
+
+        set_signer_at(accounts(2), 99);
+        assert!(contract.get_all_posts(0, 10).is_empty());
+        assert!(contract.get_recent_posts(accounts(2), 0, 10).is_empty());
+
+        set_signer_at(accounts(2), 100);
+        assert_eq!(contract.get_all_posts(0, 10).len(), 1);
+        assert_eq!(contract.get_recent_posts(accounts(2), 0, 10).len(), 1);
+    }
+
+    #[test]
+    fn get_posts_created_between() {
+        let mut contract = setup_contract();
+
+        for index in 1..=4 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        let posts = contract
+            .get_posts_created_between(2 * DEFAULT_POST_COOLDOWN, 3 * DEFAULT_POST_COOLDOWN);
+
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].created_at, 2 * DEFAULT_POST_COOLDOWN);
+        assert_eq!(posts[1].created_at, 3 * DEFAULT_POST_COOLDOWN);
+    }
+
+    #[test]
+    #[should_panic(expected = "INVALID_RANGE")]
+    fn get_posts_created_between_invalid_range() {
+        let contract = setup_contract();
+
+        contract.get_posts_created_between(30, 20);
+    }
+
+    #[test]
+    fn react() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.react(0, Reaction::Love);
+
+        assert_eq!(
+            contract.get_reaction_counts(0),
+            vec![
+                (Reaction::Like, 0),
+                (Reaction::Love, 1),
+                (Reaction::Laugh, 0),
+                (Reaction::Angry, 0),
+            ]
+        );
+
+        contract.react(0, Reaction::Laugh);
+
+        assert_eq!(
+            contract.get_reaction_counts(0),
+            vec![
+                (Reaction::Like, 0),
+                (Reaction::Love, 0),
+                (Reaction::Laugh, 1),
+                (Reaction::Angry, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn get_feed_count() {
+        let mut contract = setup_contract();
+
+        for account_id in [accounts(2), accounts(3)] {
+            for index in 0..2 {
+                set_signer_at(account_id.clone(), index * DEFAULT_POST_COOLDOWN);
+                contract.add_post(
+                    "Test".to_string(),
+                    "Test Descritpion".to_string(),
+                    "tag1,tag2,tag3".to_string(),
+                    "post".to_string(),
+                    "text".to_string(),
+                );
+            }
+        }
+
+        set_signer(accounts(1));
+        contract.follow(accounts(2));
+        contract.follow(accounts(3));
+
+        assert_eq!(contract.get_feed_count(accounts(1)), 4);
+        assert_eq!(contract.get_feed(accounts(1), 0, 2).len(), 2);
+    }
+
+    #[test]
+    fn transfer_ownership() {
+        let mut contract = setup_contract();
+
+        set_signer(accounts(0));
+        contract.transfer_ownership(accounts(2));
+
+        assert_eq!(contract.owner_id, accounts(2));
+        assert_eq!(
+            get_logs(),
+            vec![format!(
+                "EVENT_JSON:{}",
+                json!({
+                    "standard": EVENT_STANDARD,
+                    "version": EVENT_VERSION,
+                    "event": "ownership_transferred",
+                    "data": [{ "old_owner": accounts(0), "new_owner": accounts(2) }],
+                })
+            )]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_CONTRACT_OWNER")]
+    fn transfer_ownership_not_owner() {
+        let mut contract = setup_contract();
+
+        contract.transfer_ownership(accounts(1));
+    }
+
+    #[test]
+    fn get_posts_by_tag_paginated() {
+        let mut contract = setup_contract();
+
+        for index in 0..5 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        let posts = contract.get_posts_by_tag("tag1".to_string(), 1, 2);
+
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].id, 1);
+        assert_eq!(posts[1].id, 2);
+    }
+
+    #[test]
+    fn get_post_count_by_tag() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag1,tag4".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        assert_eq!(contract.get_post_count_by_tag("Tag1".to_string()), 2);
+        assert_eq!(contract.get_post_count_by_tag("tag4".to_string()), 1);
+        assert_eq!(contract.get_post_count_by_tag("unused".to_string()), 0);
+
+        // Posts hidden from listings aren't counted
+        contract.soft_delete_post(1);
+
+        assert_eq!(contract.get_post_count_by_tag("tag1".to_string()), 1);
+        assert_eq!(contract.get_post_count_by_tag("tag4".to_string()), 0);
+    }
+
+    #[test]
+    fn reported_posts_are_auto_hidden() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        for reporter_id in [accounts(2), accounts(3)] {
+            set_signer(reporter_id);
+            contract.report_post(0, "spam".to_string());
+        }
+
+        assert_eq!(contract.get_all_posts(0, 10).len(), 1);
+
+        set_signer(accounts(4));
+        contract.report_post(0, "spam".to_string());
+
+        assert!(contract.get_all_posts(0, 10).is_empty());
+
+        set_signer(accounts(0));
+        contract.clear_reports(0);
+
+        assert_eq!(contract.get_all_posts(0, 10).len(), 1);
+        assert!(contract.get_reports(0).is_empty());
+    }
+
+    #[test]
+    fn get_posts_by_owner_paginated() {
+        let mut contract = setup_contract();
+
+        for index in 1..=3 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        let posts = contract.get_posts_by_owner(accounts(1), 1, 1);
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].created_at, 2 * DEFAULT_POST_COOLDOWN);
+    }
+
+    #[test]
+    fn repeated_likes_notify_once() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_at(accounts(2), 1);
+        contract.like_a_post(0);
+        set_signer_at(accounts(2), 2);
+        contract.unlike_a_post(0);
+        set_signer_at(accounts(2), 3);
+        contract.like_a_post(0);
+
+        set_signer(accounts(1));
+        assert_eq!(contract.get_notifications(0, 10).len(), 1);
+
+        // Liking again once the window has passed notifies again
+        set_signer_at(accounts(2), 4 + DEFAULT_LIKE_NOTIFICATION_WINDOW);
+        contract.unlike_a_post(0);
+        contract.like_a_post(0);
+
+        set_signer(accounts(1));
+        assert_eq!(contract.get_notifications(0, 10).len(), 2);
+    }
+
+    #[test]
+    fn get_posts_by_ids() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        let posts = contract.get_posts_by_ids(vec![0, 99, 1]);
+
+        assert_eq!(posts.len(), 3);
+        assert_eq!(posts[0].as_ref().unwrap().id, 0);
+        assert!(posts[1].is_none());
+        assert_eq!(posts[2].as_ref().unwrap().id, 1);
+    }
+
+    #[test]
+    fn search() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Sunset".to_string(),
+            "A walk along the Beach, at dusk".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        let posts = contract.search("beach".to_string());
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, 0);
+        assert_eq!(contract.search("SUNSET".to_string()).len(), 1);
+
+        // Short words and stopwords aren't indexed
+        assert!(contract.search("at".to_string()).is_empty());
+        assert!(contract.search("the".to_string()).is_empty());
+
+        contract.delete_post(0);
+
+        assert!(contract.search("beach".to_string()).is_empty());
+        assert!(contract
+            .posts_by_keyword
+            .get(&"beach".to_string())
+            .is_none());
+    }
+
+    #[test]
+    fn get_posts_from_key() {
+        let mut contract = setup_contract();
+
+        for index in 0..4 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        let page: Vec<u128> = contract
+            .get_posts_from_key(None, 2)
+            .into_iter()
+            .map(|(post_id, _)| post_id)
+            .collect();
+        assert_eq!(page, vec![0, 1]);
+
+        // Removing a post mid-iteration doesn't shift the cursor
+        contract.delete_post(2);
+
+        let page: Vec<u128> = contract
+            .get_posts_from_key(Some(1), 2)
+            .into_iter()
+            .map(|(post_id, _)| post_id)
+            .collect();
+        assert_eq!(page, vec![3]);
+        assert!(contract.get_posts_from_key(Some(3), 2).is_empty());
+        assert!(contract.get_posts_from_key(Some(u128::MAX), 2).is_empty());
+    }
+
+    #[test]
+    fn get_top_tipped_posts() {
+        let mut contract = setup_contract();
+
+        for index in 0..3 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        set_signer_with_deposit(accounts(2), ONE_NEAR);
+        contract.tip_post(0);
+        set_signer_with_deposit(accounts(2), 2 * ONE_NEAR);
+        contract.tip_post(1);
+
+        let post_ids: Vec<u128> = contract
+            .get_top_tipped_posts(10)
+            .iter()
+            .map(|post| post.id)
+            .collect();
+
+        assert_eq!(post_ids, vec![1, 0, 2]);
+        assert_eq!(contract.get_top_tipped_posts(1).len(), 1);
+    }
+
+    #[test]
+    fn get_tippers() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_with_deposit(accounts(2), ONE_NEAR);
+        contract.tip_post(0);
+        set_signer_with_deposit(accounts(2), 2 * ONE_NEAR);
+        contract.tip_post(0);
+        set_signer_with_deposit(accounts(3), ONE_NEAR);
+        contract.tip_post(0);
+
+        assert_eq!(
+            contract.get_tippers(0),
+            vec![(accounts(2), 3 * ONE_NEAR), (accounts(3), ONE_NEAR)]
+        );
+        assert!(contract.get_tippers(1).is_empty());
+    }
+
+    #[test]
+    fn followers_only_comments() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.set_comment_policy(0, CommentPolicy::FollowersOnly);
+
+        set_signer(accounts(2));
+        contract.follow(accounts(1));
+        contract.add_comment(0, "Nice post".to_string(), None);
+
+        assert_eq!(contract.get_comments(0).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_A_FOLLOWER")]
+    fn followers_only_comments_not_follower() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.set_comment_policy(0, CommentPolicy::FollowersOnly);
+
+        set_signer(accounts(2));
+        contract.add_comment(0, "Nice post".to_string(), None);
+    }
+
+    #[test]
+    fn get_comment_count() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        assert_eq!(contract.get_comment_count(0), 0);
+
+        contract.add_comment(0, "First".to_string(), None);
+        contract.add_comment(0, "Second".to_string(), None);
+
+        assert_eq!(contract.get_comment_count(0), 2);
+    }
+
+    #[test]
+    fn feed_collapses_reposts() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_at(accounts(2), 1);
+        contract.repost(0);
+        set_signer_at(accounts(3), 2);
+        contract.repost(0);
+
+        set_signer(accounts(4));
+        contract.follow(accounts(2));
+        contract.follow(accounts(3));
+
+        let feed = contract.get_feed(accounts(4), 0, 10);
+
+        assert_eq!(feed.len(), 1);
+        assert_eq!(feed[0].owner_id, accounts(2));
+        assert_eq!(contract.get_feed_count(accounts(4)), 1);
+        assert_eq!(
+            contract
+                .get_post_by_id(0, accounts(4))
+                .expect("Post not found")
+                .repost_count,
+            2
+        );
+    }
+
+    #[test]
+    fn is_following() {
+        let mut contract = setup_contract();
+
+        assert!(!contract.is_following(accounts(1), accounts(2)));
+
+        contract.follow(accounts(2));
+
+        assert!(contract.is_following(accounts(1), accounts(2)));
+        assert!(!contract.is_following(accounts(2), accounts(1)));
+
+        contract.unfollow(accounts(2));
+
+        assert!(!contract.is_following(accounts(1), accounts(2)));
+    }
+
+    #[test]
+    fn get_engagement_score() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        for account_id in [accounts(2), accounts(3)] {
+            set_signer(account_id);
+            contract.like_a_post(0);
+            contract.increment_view_count(0);
+        }
+        contract.increment_view_count(0);
+        contract.add_comment(0, "Nice post".to_string(), None);
+
+        set_signer_with_deposit(accounts(2), 2 * ONE_NEAR);
+        contract.tip_post(0);
+
+        assert_eq!(
+            contract.get_engagement_score(0),
+            2 * LIKE_WEIGHT + COMMENT_WEIGHT + 3 * VIEW_WEIGHT + 2 * TIP_WEIGHT
+        );
+    }
+
+    #[test]
+    fn get_engagement_score_saturates() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        let mut post = contract.posts.get(&0).unwrap();
+        post.like_count = u64::MAX;
+        contract.posts.insert(&0, &post);
+
+        assert_eq!(contract.get_engagement_score(0), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "POST_NOT_FOUND")]
+    fn get_engagement_score_missing_post() {
+        let contract = setup_contract();
+
+        contract.get_engagement_score(0);
+    }
+
+    #[test]
+    fn remove_tag_from_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.remove_tag_from_post(0, "Tag2".to_string());
+        // Removing a tag the post doesn't carry is a no-op
+        contract.remove_tag_from_post(0, "tag9".to_string());
+
+        assert_eq!(contract.posts.get(&0).unwrap().tags, vec!["tag1", "tag3"]);
+        assert!(contract
+            .get_posts_by_tag("tag2".to_string(), 0, 10)
+            .is_empty());
+        assert_eq!(
+            contract.get_posts_by_tag("tag1".to_string(), 0, 10).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn add_tag_to_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.add_tag_to_post(0, " Tag9 ".to_string());
+        // Adding a tag the post already carries is a no-op
+        contract.add_tag_to_post(0, "tag9".to_string());
+
+        let posts = contract.get_posts_by_tag("tag9".to_string(), 0, 10);
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].tags, vec!["tag1", "tag2", "tag3", "tag9"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "TOO_MANY_TAGS")]
+    fn add_tag_to_post_too_many_tags() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "t0,t1,t2,t3,t4,t5,t6,t7,t8,t9".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        contract.add_tag_to_post(0, "tag10".to_string());
+    }
+
+    #[test]
+    fn get_similar_posts() {
+        let mut contract = setup_contract();
+
+        for (index, tags) in ["tag1,tag2,tag3", "tag1,tag2", "tag3", "tag9"]
+            .iter()
+            .enumerate()
+        {
+            set_signer_at(accounts(1), index as u64 * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                "Test".to_string(),
+                "Test Descritpion".to_string(),
+                tags.to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        let post_ids: Vec<u128> = contract
+            .get_similar_posts(0, 10)
+            .iter()
+            .map(|post| post.id)
+            .collect();
+
+        assert_eq!(post_ids, vec![1, 2]);
+        assert_eq!(contract.get_similar_posts(0, 1).len(), 1);
+
+        // A repost is an exact copy, so it and its original aren't similar to each other
+        set_signer(accounts(2));
+        let repost = contract.repost(0);
+
+        let post_ids: Vec<u128> = contract
+            .get_similar_posts(0, 10)
+            .iter()
+            .map(|post| post.id)
+            .collect();
+        assert_eq!(post_ids, vec![1, 2]);
+
+        let post_ids: Vec<u128> = contract
+            .get_similar_posts(repost.id, 10)
+            .iter()
+            .map(|post| post.id)
+            .collect();
+        assert_eq!(post_ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn deactivate_account() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.set_profile(
+            "Bob".to_string(),
+            "Hello".to_string(),
+            "https://example.com/bob.png".to_string(),
+        );
+
+        contract.deactivate_account();
+
+        assert!(contract.get_all_posts(0, 10).is_empty());
+        assert!(contract.get_profile(accounts(1)).is_none());
+        assert!(contract.posts.get(&0).is_some());
+
+        contract.reactivate_account();
+
+        assert_eq!(contract.get_all_posts(0, 10).len(), 1);
+        assert!(contract.get_profile(accounts(1)).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "RATE_LIMITED")]
+    fn add_post_rate_limited() {
+        set_signer(accounts(1));
+        let mut contract = SocialNetworking::new(accounts(0));
+
+        for _ in 0..2 {
+            contract.add_post(
+                "Test".to_string(),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+    }
+
+    #[test]
+    fn add_post_after_cooldown() {
+        set_signer(accounts(1));
+        let mut contract = SocialNetworking::new(accounts(0));
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
+        contract.add_post(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        assert_eq!(contract.get_all_posts(0, 10).len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "RATE_LIMITED")]
+    fn publish_draft_rate_limited() {
+        set_signer(accounts(1));
+        let mut contract = SocialNetworking::new(accounts(0));
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+        contract.add_draft(
+            "Test2".to_string(),
+            "Test Descritpion2".to_string(),
+            "tag4,tag5,tag6".to_string(),
+            "video".to_string(),
+            "video".to_string(),
+        );
+
+        contract.publish_post(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "RATE_LIMITED")]
+    fn repost_rate_limited() {
+        set_signer(accounts(1));
+        let mut contract = SocialNetworking::new(accounts(0));
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
 
-        self.number_of_posts += 1;
-        self.posts.insert(&post.id, &post);
+        set_signer(accounts(2));
+        contract.repost(0);
+        contract.repost(0);
+    }
 
-        self.add_posts_by_tag(post.clone(), tags);
-        post
+    #[test]
+    #[should_panic(expected = "TOO_MANY_POSTS")]
+    fn add_posts_too_many() {
+        let mut contract = setup_contract();
+
+        let posts = (0..=MAX_POSTS_PER_BATCH)
+            .map(|i| AddPostArgs {
+                title: format!("Test{}", i),
+                description: "Test Descritpion".to_string(),
+                tags: "tag1".to_string(),
+                media: "post".to_string(),
+                media_type: "text".to_string(),
+            })
+            .collect();
+
+        contract.add_posts(posts);
     }
 
-    #[private]
-    fn add_posts_by_tag(&mut self, post: Post, tags: Vec<String>) {
-        let mut posts_for_tag: Vec<Post>;
+    #[test]
+    fn quote_post() {
+        let mut contract = setup_contract();
 
-        for tag in tags {
-            if let None = self.posts_by_tag.get(&tag) {
-                posts_for_tag = Vec::<Post>::new();
-            } else {
-                posts_for_tag = self
-                    .posts_by_tag
-                    .get(&tag)
-                    .unwrap_or_else(|| env::panic_str("NO_POSTS_FOUND"));
-            }
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
 
-            posts_for_tag.push(post.clone());
-            self.posts_by_tag.insert(&tag, &posts_for_tag);
-        }
+        set_signer(accounts(2));
+        let quote = contract.quote_post(0, "So true #agreed".to_string());
+
+        assert_eq!(quote.id, 1);
+        assert_eq!(quote.owner_id, accounts(2));
+        assert_eq!(quote.description, "So true #agreed".to_string());
+        assert_eq!(quote.reposted_from, Some(0));
+        assert!(quote.quote);
+        assert_eq!(quote.tags, vec!["agreed"]);
+        assert_eq!(contract.posts.get(&0).unwrap().repost_count, 0);
     }
 
-    pub fn get_all_posts(&self) -> Vec<(u128, Post)> {
-        self.posts.to_vec()
+    #[test]
+    fn get_quotes_of_post() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+
+        set_signer(accounts(2));
+        contract.quote_post(0, "So true".to_string());
+        set_signer_at(accounts(2), DEFAULT_POST_COOLDOWN);
+        contract.repost(0);
+        set_signer(accounts(3));
+        contract.quote_post(0, "Not so sure".to_string());
+
+        let quotes = contract.get_quotes_of_post(0);
+
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].description, "So true".to_string());
+        assert_eq!(quotes[1].description, "Not so sure".to_string());
+        assert!(contract.get_quotes_of_post(1).is_empty());
     }
 
-    pub fn like_a_post(&mut self, post_id: u128) -> Post {
-        let post = self.posts.get(&post_id);
+    #[test]
+    fn set_report_threshold() {
+        let mut contract = setup_contract();
 
-        if let None = post {
-            return Post {
-                id: post_id,
-                title: "No post found at that ID".to_string(),
-                description: "No post found at that ID".to_string(),
-                tags: Vec::<String>::new(),
-                media: "No post found at that ID".to_string(),
-                users_who_liked: Vec::<AccountId>::new(),
-                owner_id: env::signer_account_id(),
-            };
-        }
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
 
-        // Copy and update post
-        let mut post_copy = post.unwrap_or_else(|| env::panic_str("POST_NOT_FOUND"));
+        set_signer(accounts(0));
+        contract.set_report_threshold(1);
 
-        // Update the post copy
-        post_copy.users_who_liked.push(env::signer_account_id());
+        assert_eq!(contract.report_threshold, 1);
 
-        // Update the posts state
-        self.posts.insert(&post_id, &post_copy.clone());
+        set_signer(accounts(2));
+        contract.report_post(0, "spam".to_string());
 
-        self.add_post_to_my_liked(env::signer_account_id(), &post_copy);
+        assert!(contract.get_all_posts(0, 10).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "NOT_CONTRACT_OWNER")]
+    fn set_report_threshold_not_owner() {
+        let mut contract = setup_contract();
 
-        post_copy
+        contract.set_report_threshold(1);
     }
 
-    #[private]
-    pub fn add_post_to_my_liked(&mut self, sender_id: AccountId, post: &Post) {
-        // Find the users liked posts
-        let users_likes = self.likes_by_user_id.get(&sender_id);
+    #[test]
+    fn pin_comment() {
+        let mut contract = setup_contract();
 
-        // Add post to users likes
-        if let None = users_likes {
-            // Create users likes
-            self.likes_by_user_id
-                .insert(&sender_id, &vec![post.clone()]);
-        } else {
-            // Update users likes
-            let mut checked_users_likes =
-                users_likes.unwrap_or_else(|| env::panic_str("UNABLE_TO_FIND_USERS_LIKES"));
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
 
-            checked_users_likes.push(post.clone());
+        assert!(contract.get_pinned_comment(0).is_none());
 
-            self.likes_by_user_id
-                .insert(&sender_id, &checked_users_likes);
-        }
-    }
+        set_signer(accounts(2));
+        contract.add_comment(0, "First".to_string(), None);
+        contract.add_comment(0, "Helpful".to_string(), None);
 
-    pub fn get_liked_posts(&self) -> Vec<Post> {
-        self.likes_by_user_id
-            .get(&env::signer_account_id())
-            .unwrap_or_else(|| env::panic_str("UNABLE_TO_FIND_USERS_LIKED_POSTS"))
+        set_signer(accounts(1));
+        contract.pin_comment(0, 1);
+
+        let pinned = contract.get_pinned_comment(0).expect("No pinned comment");
+
+        assert_eq!(pinned.id, 1);
+        assert_eq!(pinned.text, "Helpful".to_string());
     }
 
-    pub fn get_posts_by_tag(&self, tag: String) -> Vec<Post> {
-        self.posts_by_tag
-            .get(&tag)
-            .unwrap_or_else(|| env::panic_str("UNABLE_TO_FIND_POSTS"))
+    #[test]
+    #[should_panic(expected = "COMMENT_NOT_FOUND")]
+    fn pin_comment_from_another_post() {
+        let mut contract = setup_contract();
+
+        for index in 0..2 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                "Test".to_string(),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+        contract.add_comment(1, "First".to_string(), None);
+
+        contract.pin_comment(0, 0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn get_followers_paginated() {
+        let mut contract = setup_contract();
+
+        for account_id in [
+            accounts(0),
+            accounts(2),
+            accounts(3),
+            accounts(4),
+            accounts(5),
+        ] {
+            set_signer(account_id);
+            contract.follow(accounts(1));
+        }
+
+        assert_eq!(
+            contract.get_followers(accounts(1), 1, 3),
+            vec![accounts(2), accounts(3), accounts(4)]
+        );
+        assert_eq!(contract.get_follower_count(accounts(1)), 5);
+    }
 
     #[test]
-    fn add_post() {
-        let mut contract = SocialNetworking::default();
+    fn new_posts_fan_out_to_follower_timelines() {
+        let mut contract = setup_contract();
 
+        contract.follow(accounts(2));
+
+        set_signer(accounts(2));
         contract.add_post(
             "Test".to_string(),
             "Test Descritpion".to_string(),
             "tag1,tag2,tag3".to_string(),
             "post".to_string(),
+            "text".to_string(),
         );
 
-        let new_post = contract.posts.get(&0).expect("Issue getting post in test");
+        assert_eq!(contract.timeline.get(&accounts(1)), Some(vec![0]));
+        assert!(contract.timeline.get(&accounts(3)).is_none());
+        assert_eq!(contract.get_feed(accounts(1), 0, 10).len(), 1);
 
-        assert_eq!(contract.number_of_posts, 1);
-        assert_eq!(new_post.title, "Test".to_string());
-        assert_eq!(new_post.tags, vec!["tag1", "tag2", "tag3"]);
+        // Unfollowing doesn't prune the timeline
+        set_signer(accounts(1));
+        contract.unfollow(accounts(2));
 
-        assert_eq!(
-            contract
-                .posts_by_tag
-                .get(&"tag1".to_string())
-                .expect("Error finding posts by tag in test")
-                .get(0)
-                .expect("Error getting first post in test")
-                .title,
-            "Test".to_string()
-        )
+        assert_eq!(contract.get_feed(accounts(1), 0, 10).len(), 1);
     }
 
     #[test]
-    fn get_all_posts() {
-        let mut contract = SocialNetworking::default();
+    fn get_stats() {
+        let mut contract = setup_contract();
 
         contract.add_post(
             "Test".to_string(),
             "Test Descritpion".to_string(),
             "tag1,tag2,tag3".to_string(),
             "post".to_string(),
+            "text".to_string(),
         );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
         contract.add_post(
             "Test2".to_string(),
             "Test Descritpion2".to_string(),
-            "tag4,tag5,tag6".to_string(),
+            "tag1,tag4".to_string(),
+            "video".to_string(),
             "video".to_string(),
         );
+
+        contract.like_a_post(0);
+        set_signer(accounts(2));
+        contract.like_a_post(0);
+        contract.like_a_post(1);
+
+        let stats = contract.get_stats();
+
+        assert_eq!(stats.total_posts, 2);
+        assert_eq!(stats.total_tags, 4);
+        assert_eq!(stats.total_likes, 3);
+    }
+
+    #[test]
+    fn transfer_post() {
+        let mut contract = setup_contract();
+
         contract.add_post(
-            "Test3".to_string(),
-            "Test Descritpion3".to_string(),
-            "tag1,tag5,tag7".to_string(),
-            "pic".to_string(),
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
         );
+        contract.pin_post(0);
 
-        let all_posts = contract.get_all_posts();
+        contract.transfer_post(0, accounts(2));
 
-        assert_eq!(all_posts.len(), 3);
-        assert_eq!(
-            all_posts
-                .get(2)
-                .expect("Trouble getting all posts in test")
-                .1
-                .title,
-            "Test3"
-        );
+        assert!(contract.get_posts_by_owner(accounts(1), 0, 10).is_empty());
+        assert!(contract.get_pinned_post(accounts(1)).is_none());
+
+        let posts = contract.get_posts_by_owner(accounts(2), 0, 10);
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].owner_id, accounts(2));
     }
 
     #[test]
-    fn like_a_post() {
-        let mut contract = SocialNetworking::default();
+    #[should_panic(expected = "ALREADY_POST_OWNER")]
+    fn transfer_post_to_current_owner() {
+        let mut contract = setup_contract();
 
         contract.add_post(
             "Test".to_string(),
             "Test Descritpion".to_string(),
             "tag1,tag2,tag3".to_string(),
             "post".to_string(),
+            "text".to_string(),
         );
 
-        contract.like_a_post(0);
+        contract.transfer_post(0, accounts(1));
+    }
 
-        let liked_post = contract.posts.get(&0).expect("Post not liked");
+    #[test]
+    fn like_posts() {
+        let mut contract = setup_contract();
 
-        assert_eq!(liked_post.users_who_liked.len(), 1);
-        assert_eq!(
-            liked_post
-                .users_who_liked
-                .get(0)
-                .expect("Error finding uses who liked the post"),
-            &env::signer_account_id()
-        );
+        for index in 0..3 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        set_signer(accounts(2));
+        contract.like_a_post(2);
+
+        let liked_posts = contract.like_posts(vec![0, 1, 99, 2]);
+        let post_ids: Vec<u128> = liked_posts.iter().map(|post| post.id).collect();
+
+        assert_eq!(post_ids, vec![0, 1]);
+        assert!(contract.has_liked(0, accounts(2)));
+        assert!(contract.has_liked(1, accounts(2)));
+        assert_eq!(contract.posts.get(&2).unwrap().like_count, 1);
     }
 
     #[test]
-    fn get_liked_posts() {
-        let mut contract = SocialNetworking::default();
+    fn get_posts_with_media() {
+        let mut contract = setup_contract();
 
         contract.add_post(
             "Test".to_string(),
             "Test Descritpion".to_string(),
             "tag1,tag2,tag3".to_string(),
-            "post".to_string(),
+            "".to_string(),
+            "text".to_string(),
         );
+        set_signer_at(accounts(1), DEFAULT_POST_COOLDOWN);
         contract.add_post(
             "Test2".to_string(),
             "Test Descritpion2".to_string(),
             "tag4,tag5,tag6".to_string(),
-            "video".to_string(),
-        );
-        contract.add_post(
-            "Test3".to_string(),
-            "Test Descritpion3".to_string(),
-            "tag1,tag5,tag7".to_string(),
-            "pic".to_string(),
+            "pic.png".to_string(),
+            "image".to_string(),
         );
 
+        let posts = contract.get_posts_with_media(0, 10);
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, 1);
+        assert!(contract.get_posts_with_media(1, 10).is_empty());
+    }
+
+    #[test]
+    fn get_total_likes_received() {
+        let mut contract = setup_contract();
+
+        for index in 0..2 {
+            set_signer_at(accounts(1), index * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        set_signer(accounts(2));
         contract.like_a_post(0);
         contract.like_a_post(1);
+        set_signer(accounts(3));
+        contract.like_a_post(1);
 
-        assert_eq!(
-            contract
-                .get_liked_posts()
-                .get(0)
-                .unwrap_or_else(|| env::panic_str("ERROR FINDING LIKED POSTS"))
-                .title,
-            "Test".to_string()
+        assert_eq!(contract.get_total_likes_received(accounts(1)), 3);
+        assert_eq!(contract.get_total_likes_received(accounts(2)), 0);
+    }
+
+    #[test]
+    fn edit_post_reindexes_mentions() {
+        let mut contract = setup_contract();
+
+        contract.add_post(
+            "Test".to_string(),
+            "Thanks @charlie.near and @danny.near".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
         );
-        assert_eq!(contract.get_liked_posts().len(), 2);
+
+        contract.edit_post(
+            0,
+            "Test".to_string(),
+            "Thanks @danny.near and @eugene.near".to_string(),
+            "post".to_string(),
+        );
+
+        assert!(contract.get_mentions(accounts(2)).is_empty());
+        assert_eq!(contract.get_mentions(accounts(3)).len(), 1);
+        assert_eq!(contract.get_mentions(accounts(4)).len(), 1);
+
+        // Accounts that were already mentioned aren't notified twice
+        set_signer(accounts(3));
+        assert_eq!(contract.get_notifications(0, 10).len(), 1);
+        set_signer(accounts(4));
+        assert_eq!(contract.get_notifications(0, 10).len(), 1);
     }
 
     #[test]
-    fn get_posts_by_tag() {
-        let mut contract = SocialNetworking::default();
+    #[should_panic(expected = "TOO_MANY_TAGS")]
+    fn add_post_too_many_tags() {
+        let mut contract = setup_contract();
+
+        // Hashtags count towards the limit
+        contract.add_post(
+            "Test".to_string(),
+            "Test Descritpion #t10".to_string(),
+            "t0,t1,t2,t3,t4,t5,t6,t7,t8,t9".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "TOO_MANY_TAGS")]
+    fn update_post_tags_too_many_tags() {
+        let mut contract = setup_contract();
 
         contract.add_post(
             "Test".to_string(),
             "Test Descritpion".to_string(),
             "tag1,tag2,tag3".to_string(),
             "post".to_string(),
+            "text".to_string(),
         );
+
+        contract.update_post_tags(0, "t0,t1,t2,t3,t4,t5,t6,t7,t8,t9,t10".to_string());
+    }
+
+    #[test]
+    fn follow_backfill_is_bounded() {
+        let mut contract = setup_contract();
+
+        for index in 0..MAX_TIMELINE_BACKFILL + 5 {
+            set_signer_at(accounts(2), index as u64 * DEFAULT_POST_COOLDOWN);
+            contract.add_post(
+                format!("Test{}", index),
+                "Test Descritpion".to_string(),
+                "tag1,tag2,tag3".to_string(),
+                "post".to_string(),
+                "text".to_string(),
+            );
+        }
+
+        set_signer(accounts(1));
+        contract.follow(accounts(2));
+
+        let timeline = contract.timeline.get(&accounts(1)).unwrap();
+
+        assert_eq!(timeline.len(), MAX_TIMELINE_BACKFILL);
+        assert!(timeline.contains(&(MAX_TIMELINE_BACKFILL as u128 + 4)));
+        assert!(!timeline.contains(&0));
+    }
+
+    #[test]
+    fn deactivated_author_hidden_from_account_lists() {
+        let mut contract = setup_contract();
+
         contract.add_post(
-            "Test2".to_string(),
-            "Test Descritpion2".to_string(),
-            "tag4,tag5,tag6".to_string(),
-            "video".to_string(),
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
         );
-        contract.add_post(
-            "Test3".to_string(),
-            "Test Descritpion3".to_string(),
-            "tag1,tag5,tag7".to_string(),
-            "pic".to_string(),
+        contract.pin_post(0);
+
+        set_signer(accounts(2));
+        contract.like_a_post(0);
+        contract.bookmark_post(0);
+
+        set_signer(accounts(1));
+        contract.deactivate_account();
+
+        set_signer(accounts(2));
+        assert!(contract.get_pinned_post(accounts(1)).is_none());
+        assert!(contract.get_liked_posts_for_account(accounts(2)).is_empty());
+        assert!(contract.get_bookmarks().is_empty());
+    }
+
+    #[test]
+    fn followers_only_post_hidden_from_account_lists() {
+        let mut contract = setup_contract();
+
+        contract.add_post_with_visibility(
+            "Test".to_string(),
+            "Test Descritpion".to_string(),
+            "tag1,tag2,tag3".to_string(),
+            "post".to_string(),
+            "text".to_string(),
+            "followers_only".to_string(),
         );
+        contract.pin_post(0);
 
-        let posts = contract.get_posts_by_tag("tag5".to_string());
+        set_signer(accounts(2));
+        contract.follow(accounts(1));
+        contract.like_a_post(0);
+        contract.bookmark_post(0);
 
-        assert_eq!(posts.len(), 2);
-        assert_eq!(posts.get(0).unwrap().title, "Test2".to_string());
-        assert_eq!(posts.get(1).unwrap().title, "Test3".to_string());
+        // The follower can read back what they liked and bookmarked
+        assert_eq!(contract.get_liked_posts().len(), 1);
+        assert_eq!(contract.get_bookmarks().len(), 1);
+
+        // Views without a viewer only show public posts
+        set_signer(accounts(3));
+        assert!(contract.get_pinned_post(accounts(1)).is_none());
+        assert!(contract.get_liked_posts_for_account(accounts(2)).is_empty());
+
+        // Once they stop following, the post drops out of their own lists too
+        set_signer(accounts(2));
+        contract.unfollow(accounts(1));
+
+        assert!(contract.get_liked_posts().is_empty());
+        assert!(contract.get_bookmarks().is_empty());
     }
 }